@@ -0,0 +1,109 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// per thread, so days running in parallel do not count each other's allocations
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static BYTES: Cell<usize> = const { Cell::new(0) };
+    // relative to the start of the measurement, so it goes negative when older memory is freed
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct AllocationStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak_bytes: usize,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Allocations {
+    pub parse: AllocationStats,
+    pub part1: Option<AllocationStats>,
+    pub part2: Option<AllocationStats>,
+}
+
+impl Allocations {
+    pub fn phases(&self) -> [(&'static str, Option<AllocationStats>); 3] {
+        [("parse", Some(self.parse)), ("part1", self.part1), ("part2", self.part2)]
+    }
+}
+
+// counts allocations while enabled, has to be installed with #[global_allocator] by the binary
+pub struct CountingAllocator;
+
+fn allocated(size: usize) {
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size));
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + size as isize);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+fn freed(size: usize) {
+    let _ = LIVE.try_with(|live| live.set(live.get() - size as isize));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() && ENABLED.load(Ordering::Relaxed) {
+            allocated(layout.size())
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        if ENABLED.load(Ordering::Relaxed) {
+            freed(layout.size())
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() && ENABLED.load(Ordering::Relaxed) {
+            allocated(layout.size())
+        }
+        pointer
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() && ENABLED.load(Ordering::Relaxed) {
+            freed(layout.size());
+            allocated(new_size)
+        }
+        new_pointer
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed)
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// the allocations made by the current thread while running `f`
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    ALLOCATIONS.set(0);
+    BYTES.set(0);
+    LIVE.set(0);
+    PEAK.set(0);
+
+    let result = f();
+
+    let stats = AllocationStats {
+        allocations: ALLOCATIONS.get(),
+        bytes: BYTES.get(),
+        peak_bytes: PEAK.get().max(0) as usize,
+    };
+    (result, stats)
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    New,
+}
+
+impl Check {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Check::Pass => "PASS",
+            Check::Fail { .. } => "FAIL",
+            Check::New => "NEW",
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            _ => f.write_str(self.as_str())
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Expected {
+    fn part_mut(&mut self, part: usize) -> &mut Option<String> {
+        if part == 1 { &mut self.part1 } else { &mut self.part2 }
+    }
+}
+
+// stored as a small subset of TOML - one table per input file, e.g. ["inputfiles/day5"], with part1 and part2 keys
+#[derive(Debug)]
+pub struct Answers {
+    path: PathBuf,
+    record: bool,
+    dirty: bool,
+    entries: BTreeMap<String, Expected>,
+}
+
+impl Default for Answers {
+    fn default() -> Self {
+        Self { path: PathBuf::from("answers.toml"), record: false, dirty: false, entries: BTreeMap::new() }
+    }
+}
+
+fn unquote(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next()? {
+                'n' => unquoted.push('\n'),
+                't' => unquoted.push('\t'),
+                escaped @ ('"' | '\\') => unquoted.push(escaped),
+                _ => return None
+            }
+        } else {
+            unquoted.push(c)
+        }
+    }
+
+    Some(unquoted)
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn parse_entries(contents: &str) -> Result<BTreeMap<String, Expected>, String> {
+    let mut entries = BTreeMap::new();
+    let mut current: Option<String> = None;
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let invalid = || format!("line {}: cannot understand '{}'", number + 1, line);
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            let section = section.trim();
+            let name = unquote(section).unwrap_or_else(|| section.to_string());
+            entries.entry(name.clone()).or_insert_with(Expected::default);
+            current = Some(name);
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+        let value = if value.starts_with('"') {
+            unquote(value).ok_or_else(invalid)?
+        } else {
+            value.to_string()
+        };
+        let section = current.as_ref().ok_or_else(invalid)?;
+        let expected = entries.get_mut(section).expect("section was created when seen");
+
+        match key.trim() {
+            "part1" => expected.part1 = Some(value),
+            "part2" => expected.part2 = Some(value),
+            _ => return Err(invalid())
+        }
+    }
+
+    Ok(entries)
+}
+
+impl Answers {
+    pub fn load(path: &Path, record: bool) -> Result<Self, String> {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(contents) => parse_entries(&contents).map_err(|error| format!("{}: {}", path.display(), error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(format!("Could not read answers {}, due to {}", path.display(), error))
+        };
+
+        Ok(Self { path: path.to_path_buf(), record, dirty: false, entries })
+    }
+
+    pub fn check(&mut self, input: &str, part: usize, answer: &str) -> Check {
+        let expected = self.entries.get(input).and_then(|expected| {
+            if part == 1 { expected.part1.as_ref() } else { expected.part2.as_ref() }
+        });
+
+        match expected {
+            Some(expected) if expected == answer => Check::Pass,
+            Some(expected) => Check::Fail { expected: expected.clone() },
+            None => {
+                if self.record {
+                    *self.entries.entry(input.to_string()).or_default().part_mut(part) = Some(answer.to_string());
+                    self.dirty = true;
+                }
+                Check::New
+            }
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut contents = String::new();
+        for (input, expected) in &self.entries {
+            if !contents.is_empty() {
+                contents.push('\n');
+            }
+            writeln!(contents, "[{}]", quote(input)).unwrap();
+            for (key, value) in [("part1", &expected.part1), ("part2", &expected.part2)] {
+                if let Some(value) = value {
+                    writeln!(contents, "{} = {}", key, quote(value)).unwrap();
+                }
+            }
+        }
+
+        std::fs::write(&self.path, contents)
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::output::{DayRecord, Status};
+
+const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Debug, Clone)]
+pub struct Regression {
+    pub day: String,
+    pub phase: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    pub fn percent_slower(&self) -> f64 {
+        100.0 * (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0)
+    }
+}
+
+// per year and day, e.g. `2023/day5`, the nanoseconds taken by each phase that was run
+#[derive(Debug, Default)]
+pub struct Baseline {
+    days: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+// so a baseline of one year is never compared to the days of another
+fn key(record: &DayRecord) -> String {
+    format!("{}/{}", record.year, record.label())
+}
+
+fn phase_timings(record: &DayRecord) -> impl Iterator<Item=(&'static str, Duration)> + '_ {
+    [
+        (PHASES[0], Some(record.parse)),
+        (PHASES[1], record.part1.as_ref().map(|_| record.part1_duration)),
+        (PHASES[2], record.part2.as_ref().map(|_| record.part2_duration)),
+    ].into_iter().filter_map(|(phase, duration)| Some((phase, duration?)))
+}
+
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        self.input = self.input.trim_start()
+    }
+
+    fn expect(&mut self, token: char) -> Result<(), String> {
+        self.skip_whitespace();
+        self.input = self.input.strip_prefix(token)
+            .ok_or_else(|| format!("expected '{}' at '{:.20}'", token, self.input))?;
+        Ok(())
+    }
+
+    fn try_consume(&mut self, token: char) -> bool {
+        self.skip_whitespace();
+        if let Some(rest) = self.input.strip_prefix(token) {
+            self.input = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let end = self.input.find('"').ok_or("unterminated string")?;
+        let value = &self.input[..end];
+        if value.contains('\\') {
+            return Err(format!("unsupported escape in '{}'", value));
+        }
+        self.input = &self.input[end + 1..];
+        Ok(value.to_string())
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        self.skip_whitespace();
+        let end = self.input.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.input.len());
+        let value = self.input[..end].parse().map_err(|_| format!("expected a number at '{:.20}'", self.input))?;
+        self.input = &self.input[end..];
+        Ok(value)
+    }
+
+    fn object<T>(&mut self, mut value: impl FnMut(&mut Self) -> Result<T, String>) -> Result<BTreeMap<String, T>, String> {
+        let mut entries = BTreeMap::new();
+        self.expect('{')?;
+        if self.try_consume('}') {
+            return Ok(entries);
+        }
+
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            entries.insert(key, value(self)?);
+            if !self.try_consume(',') {
+                self.expect('}')?;
+                return Ok(entries);
+            }
+        }
+    }
+}
+
+impl Baseline {
+    pub fn from_records(records: &[DayRecord]) -> Self {
+        let days = records.iter()
+            .filter(|record| record.status() == Status::Solved)
+            .map(|record| {
+                let phases = phase_timings(record)
+                    .map(|(phase, duration)| (format!("{}_ns", phase), duration.as_nanos() as u64))
+                    .collect();
+                (key(record), phases)
+            })
+            .collect();
+
+        Self { days }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| format!("Could not read baseline {}, due to {}", path.display(), error))?;
+        let mut parser = Parser { input: &contents };
+        let days = parser.object(|parser| parser.object(Parser::number))
+            .map_err(|error| format!("Could not parse baseline {}: {}", path.display(), error))?;
+
+        Ok(Self { days })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut contents = String::from("{\n");
+        for (index, (day, phases)) in self.days.iter().enumerate() {
+            let separator = if index + 1 < self.days.len() { "," } else { "" };
+            let phases = phases.iter()
+                .map(|(phase, nanos)| format!("\"{}\": {}", phase, nanos))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(contents, "  \"{}\": {{{}}}{}", day, phases, separator).unwrap();
+        }
+        contents.push_str("}\n");
+
+        std::fs::write(path, contents)
+    }
+
+    pub fn regressions(&self, records: &[DayRecord], threshold_percent: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+
+        for record in records.iter().filter(|record| record.status() == Status::Solved) {
+            let key = key(record);
+            let Some(phases) = self.days.get(&key) else {
+                continue;
+            };
+
+            for (phase, current) in phase_timings(record) {
+                let Some(&baseline) = phases.get(&format!("{}_ns", phase)) else {
+                    continue;
+                };
+                let baseline = Duration::from_nanos(baseline);
+                let regression = Regression { day: key.clone(), phase, baseline, current };
+
+                if !baseline.is_zero() && regression.percent_slower() > threshold_percent {
+                    regressions.push(regression)
+                }
+            }
+        }
+
+        regressions
+    }
+}
@@ -0,0 +1,89 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::cli::PartSelection;
+use crate::error::ParseContext;
+use crate::puzzle::Puzzle;
+
+#[derive(Debug, Copy, Clone)]
+pub struct PhaseStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl PhaseStats {
+    fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = |percentile: usize| samples[(samples.len() * percentile).div_ceil(100).max(1) - 1];
+
+        Some(Self {
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct BenchStats {
+    pub iterations: usize,
+    pub parse: PhaseStats,
+    pub part1: Option<PhaseStats>,
+    pub part2: Option<PhaseStats>,
+}
+
+impl BenchStats {
+    pub fn phases(&self) -> [(&'static str, Option<PhaseStats>); 3] {
+        [("parse", Some(self.parse)), ("part1", self.part1), ("part2", self.part2)]
+    }
+}
+
+pub fn warmup_iterations(iterations: usize) -> usize {
+    (iterations / 10).max(1)
+}
+
+pub fn run<P: Puzzle>(
+    iterations: usize,
+    parts: PartSelection,
+    input: &str,
+    puzzle: &P,
+) -> Option<BenchStats> {
+    let warmup = warmup_iterations(iterations);
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut part1_samples = Vec::with_capacity(iterations);
+    let mut part2_samples = Vec::with_capacity(iterations);
+
+    for iteration in 0..warmup + iterations {
+        let input = input.to_string();
+        let start = Instant::now();
+        let parsed = puzzle.parse(input, &mut ParseContext::default()).ok()?;
+        let after_parse = Instant::now();
+        if parts.includes_part1() {
+            black_box(puzzle.part1(&parsed));
+        }
+        let after_p1 = Instant::now();
+        if parts.includes_part2() {
+            black_box(puzzle.part2(&parsed));
+        }
+        let after_p2 = Instant::now();
+
+        if iteration >= warmup {
+            parse_samples.push(after_parse - start);
+            part1_samples.push(after_p1 - after_parse);
+            part2_samples.push(after_p2 - after_p1);
+        }
+    }
+
+    Some(BenchStats {
+        iterations,
+        parse: PhaseStats::from_samples(parse_samples)?,
+        part1: parts.includes_part1().then(|| PhaseStats::from_samples(part1_samples)).flatten(),
+        part2: parts.includes_part2().then(|| PhaseStats::from_samples(part2_samples)).flatten(),
+    })
+}
@@ -0,0 +1,335 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::input::{InputSource, Inputs};
+use crate::output::Format;
+
+pub const DAYS: RangeInclusive<usize> = 1..=25;
+pub const TOTAL_BUDGET: Duration = Duration::from_secs(1);
+pub const DAY_BUDGET: Duration = Duration::from_millis(40);
+pub const DEFAULT_YEAR: u16 = 2023;
+pub const FIRST_YEAR: u16 = 2015;
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const FETCH_DELAY: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum PartSelection {
+    #[default]
+    Both,
+    Part1,
+    Part2,
+}
+
+impl PartSelection {
+    pub fn includes_part1(self) -> bool {
+        self != PartSelection::Part2
+    }
+
+    pub fn includes_part2(self) -> bool {
+        self != PartSelection::Part1
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub days: Vec<usize>,
+    pub year: u16,
+    pub parts: PartSelection,
+    pub inputs: Inputs,
+    pub format: Format,
+    pub answers: PathBuf,
+    pub record: bool,
+    pub bench: Option<usize>,
+    pub save_baseline: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    pub report: Option<PathBuf>,
+    pub threshold: f64,
+    pub budget: Duration,
+    pub day_budget: Duration,
+    pub threads: Option<usize>,
+    pub strict: bool,
+    pub raw_input: bool,
+    pub allocations: bool,
+    pub timeout: Option<Duration>,
+    pub watch: Option<usize>,
+    pub example: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct UsageError(String);
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+pub const USAGE: &str = "\
+usage: aoc2023 [DAY|FROM-TO]... [--year YEAR] [--part 1|2] [--input-dir DIR] [--input DAY=PATH]...
+               [--format text|json|csv] [--answers FILE] [--record]
+               [--bench N] [--save-baseline FILE] [--compare FILE] [--threshold PERCENT]
+               [--report FILE] [--budget MS] [--day-budget MS] [--parallel[=THREADS]] [--strict]
+               [--raw-input] [--timeout MS] [--allocations] [--watch DAY [--example FILE]]
+       aoc2023 fetch [DAY|FROM-TO]... [--year YEAR] [--input-dir DIR] [--base-url URL] [--session TOKEN] [--delay MS]
+
+    DAY, FROM-TO            days to run, e.g. `5` or `12-14`. Runs all days if omitted
+    --year YEAR             the event to run, default 2023. Inputs are read from `inputfiles/YEAR`, for 2023 from
+                            `inputfiles` itself unless `inputfiles/2023` exists
+    --part 1|2              only run the given part of each selected day
+    --input-dir DIR         read inputs from DIR instead of `inputfiles`
+    --input DAY=PATH        read the input for DAY from PATH, or from stdin if PATH is `-`
+    --format FORMAT         print results as human readable `text` (default), JSON lines or CSV
+    --answers FILE          check answers against FILE instead of `answers.toml`
+    --record                store answers for inputs that have none recorded yet
+    --bench N               time every phase over N runs after a short warmup and report
+                            min/median/p95/max, with the medians used as the day's timing
+    --save-baseline FILE    store the timing of every phase in FILE
+    --compare FILE          report every phase that got slower than in the baseline FILE
+    --threshold PERCENT     how much slower a phase may get before it is reported, default 10
+    --report FILE           write a performance report of the run to FILE, as HTML if it ends in .html, else Markdown
+    --budget MS             the time all days together may take, default 1000. The run fails if it is exceeded
+    --day-budget MS         the time a day may take unless it declares its own budget, default 40
+    --parallel[=THREADS]    run days on a pool of THREADS threads, one per cpu if not given
+    --strict                fail days that leave unparsed input behind, instead of warning about it
+    --raw-input             do not convert CRLF to LF, drop a byte order mark or trim trailing whitespace
+    --timeout MS            give up on a day that takes longer than this, report it as TIMEOUT and continue
+    --allocations           count the allocations, allocated bytes and peak memory of every phase
+    --watch DAY             re-run DAY whenever its input changes, showing how answers and timings changed
+    --example FILE          with --watch, also watch and run FILE as an example input";
+
+pub const FETCH_USAGE: &str = "\
+usage: aoc2023 fetch [DAY|FROM-TO]... [--year YEAR] [--input-dir DIR] [--base-url URL] [--session TOKEN] [--delay MS]
+
+    DAY, FROM-TO            days to download, e.g. `5` or `12-14`. Downloads all days if omitted
+    --year YEAR             the event to download inputs of, default 2023
+    --input-dir DIR         store inputs in DIR/YEAR instead of `inputfiles/YEAR`, for 2023 in DIR itself unless
+                            DIR/2023 exists. Inputs already there are never downloaded again
+    --base-url URL          download from URL/YEAR/day/N/input, default https://adventofcode.com
+    --session TOKEN         the session cookie to authenticate with, default the AOC_SESSION environment variable
+    --delay MS              the time to wait between two downloads, default 3000";
+
+fn parse_day(arg: &str) -> Result<usize, UsageError> {
+    match arg.parse() {
+        Ok(day) if DAYS.contains(&day) => Ok(day),
+        _ => Err(UsageError(format!("'{}' is not a day between {} and {}", arg, DAYS.start(), DAYS.end())))
+    }
+}
+
+fn parse_days(arg: &str, target: &mut Vec<usize>) -> Result<(), UsageError> {
+    if let Some((from, to)) = arg.split_once('-') {
+        let from = parse_day(from)?;
+        let to = parse_day(to)?;
+        if from > to {
+            return Err(UsageError(format!("empty day range '{}'", arg)));
+        }
+        target.extend(from..=to)
+    } else {
+        target.push(parse_day(arg)?)
+    }
+
+    Ok(())
+}
+
+fn parse_year(arg: &str) -> Result<u16, UsageError> {
+    match arg.parse() {
+        Ok(year) if year >= FIRST_YEAR => Ok(year),
+        _ => Err(UsageError(format!("'{}' is not an Advent of Code year", arg)))
+    }
+}
+
+fn parse_part(arg: &str) -> Result<PartSelection, UsageError> {
+    match arg {
+        "1" => Ok(PartSelection::Part1),
+        "2" => Ok(PartSelection::Part2),
+        _ => Err(UsageError(format!("'{}' is not a valid part, expected 1 or 2", arg)))
+    }
+}
+
+fn parse_input(arg: &str, inputs: &mut Inputs) -> Result<(), UsageError> {
+    let (day, path) = arg.split_once('=')
+        .ok_or_else(|| UsageError(format!("'{}' is not of the form DAY=PATH", arg)))?;
+    let day = parse_day(day)?;
+    let source = if path == "-" {
+        if inputs.reads_stdin() {
+            return Err(UsageError("only one day can read its input from stdin".to_string()));
+        }
+        InputSource::Stdin
+    } else {
+        InputSource::File(PathBuf::from(path))
+    };
+
+    inputs.set_override(day, source);
+    Ok(())
+}
+
+fn parse_count(arg: &str) -> Result<usize, UsageError> {
+    match arg.parse() {
+        Ok(iterations) if iterations > 0 => Ok(iterations),
+        _ => Err(UsageError(format!("'{}' is not a positive number", arg)))
+    }
+}
+
+fn parse_threshold(arg: &str) -> Result<f64, UsageError> {
+    match arg.trim_end_matches('%').parse() {
+        Ok(threshold) if threshold >= 0.0 => Ok(threshold),
+        _ => Err(UsageError(format!("'{}' is not a valid percentage", arg)))
+    }
+}
+
+fn parse_millis(arg: &str) -> Result<Duration, UsageError> {
+    match arg.trim_end_matches("ms").parse() {
+        Ok(millis) if millis > 0 => Ok(Duration::from_millis(millis)),
+        _ => Err(UsageError(format!("'{}' is not a positive number of milliseconds", arg)))
+    }
+}
+
+fn option_value(name: &str, inline: Option<&str>, args: &mut impl Iterator<Item=String>) -> Result<String, UsageError> {
+    match inline {
+        Some(value) => Ok(value.to_string()),
+        None => args.next().ok_or_else(|| UsageError(format!("{} requires a value", name)))
+    }
+}
+
+#[derive(Debug)]
+pub struct FetchOptions {
+    pub days: Vec<usize>,
+    pub year: u16,
+    pub directory: PathBuf,
+    pub base_url: String,
+    pub session: Option<String>,
+    pub delay: Duration,
+}
+
+fn sorted_days(mut days: Vec<usize>) -> Vec<usize> {
+    if days.is_empty() {
+        days.extend(DAYS)
+    }
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+impl FetchOptions {
+    pub fn parse(args: impl IntoIterator<Item=String>) -> Result<Self, UsageError> {
+        let mut days = Vec::new();
+        let mut year = DEFAULT_YEAR;
+        let mut directory = PathBuf::from("inputfiles");
+        let mut base_url = BASE_URL.to_string();
+        let mut session = std::env::var("AOC_SESSION").ok();
+        let mut delay = FETCH_DELAY;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                parse_days(&arg, &mut days)?;
+                continue;
+            }
+
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg.as_str(), None)
+            };
+
+            match name {
+                "--year" => year = parse_year(&option_value(name, inline, &mut args)?)?,
+                "--input-dir" => directory = option_value(name, inline, &mut args)?.into(),
+                "--base-url" => base_url = option_value(name, inline, &mut args)?.trim_end_matches('/').to_string(),
+                "--session" => session = Some(option_value(name, inline, &mut args)?),
+                "--delay" => delay = parse_millis(&option_value(name, inline, &mut args)?)?,
+                _ => return Err(UsageError(format!("unknown option '{}'", arg)))
+            }
+        }
+
+        Ok(Self { days: sorted_days(days), year, directory, base_url, session, delay })
+    }
+}
+
+impl Options {
+    pub fn parse(args: impl IntoIterator<Item=String>) -> Result<Self, UsageError> {
+        let mut days = Vec::new();
+        let mut year = DEFAULT_YEAR;
+        let mut parts = PartSelection::Both;
+        let mut inputs = Inputs::default();
+        let mut format = Format::Text;
+        let mut answers = PathBuf::from("answers.toml");
+        let mut record = false;
+        let mut bench = None;
+        let mut save_baseline = None;
+        let mut compare = None;
+        let mut report = None;
+        let mut threshold = 10.0;
+        let mut budget = TOTAL_BUDGET;
+        let mut day_budget = DAY_BUDGET;
+        let mut threads = None;
+        let mut strict = false;
+        let mut raw_input = false;
+        let mut allocations = false;
+        let mut timeout = None;
+        let mut watch = None;
+        let mut example = None;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                parse_days(&arg, &mut days)?;
+                continue;
+            }
+
+            if arg == "--record" {
+                record = true;
+                continue;
+            }
+            if arg == "--strict" {
+                strict = true;
+                continue;
+            }
+            if arg == "--raw-input" {
+                raw_input = true;
+                continue;
+            }
+            if arg == "--allocations" {
+                allocations = true;
+                continue;
+            }
+            if arg == "--parallel" {
+                threads = Some(thread::available_parallelism().map_or(1, |threads| threads.get()));
+                continue;
+            }
+
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg.as_str(), None)
+            };
+
+            match name {
+                "--year" => year = parse_year(&option_value(name, inline, &mut args)?)?,
+                "--part" => parts = parse_part(&option_value(name, inline, &mut args)?)?,
+                "--input-dir" => inputs.set_directory(option_value(name, inline, &mut args)?.into()),
+                "--input" => parse_input(&option_value(name, inline, &mut args)?, &mut inputs)?,
+                "--answers" => answers = option_value(name, inline, &mut args)?.into(),
+                "--bench" => bench = Some(parse_count(&option_value(name, inline, &mut args)?)?),
+                "--save-baseline" => save_baseline = Some(option_value(name, inline, &mut args)?.into()),
+                "--compare" => compare = Some(option_value(name, inline, &mut args)?.into()),
+                "--report" => report = Some(option_value(name, inline, &mut args)?.into()),
+                "--threshold" => threshold = parse_threshold(&option_value(name, inline, &mut args)?)?,
+                "--budget" => budget = parse_millis(&option_value(name, inline, &mut args)?)?,
+                "--day-budget" => day_budget = parse_millis(&option_value(name, inline, &mut args)?)?,
+                "--timeout" => timeout = Some(parse_millis(&option_value(name, inline, &mut args)?)?),
+                "--parallel" => threads = Some(parse_count(&option_value(name, inline, &mut args)?)?),
+                "--watch" => watch = Some(parse_day(&option_value(name, inline, &mut args)?)?),
+                "--example" => example = Some(option_value(name, inline, &mut args)?.into()),
+                "--format" => format = option_value(name, inline, &mut args)?.parse().map_err(UsageError)?,
+                _ => return Err(UsageError(format!("unknown option '{}'", arg)))
+            }
+        }
+
+        if example.is_some() && watch.is_none() {
+            return Err(UsageError("--example can only be used together with --watch".to_string()));
+        }
+
+        Ok(Self { days: sorted_days(days), year, parts, inputs, format, answers, record, bench, save_baseline, compare, report, threshold, budget, day_budget, threads, strict, raw_input, allocations, timeout, watch, example })
+    }
+}
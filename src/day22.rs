@@ -1 +0,0 @@
-solution!();
\ No newline at end of file
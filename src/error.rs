@@ -0,0 +1,80 @@
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::util::GridError;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolveError {
+    Io { input: String, message: String },
+    Parse { line: usize, column: usize, message: String },
+    DanglingInput { line: usize, column: usize, offending_line: String },
+    PartPanicked { phase: &'static str, message: String },
+    Timeout { limit: Duration },
+}
+
+impl SolveError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SolveError::Io { .. } => "io",
+            SolveError::Parse { .. } => "parse",
+            SolveError::DanglingInput { .. } => "dangling_input",
+            SolveError::PartPanicked { .. } => "panicked",
+            SolveError::Timeout { .. } => "timeout",
+        }
+    }
+
+    pub fn panicked(phase: &'static str, payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown panic payload".to_string()
+        };
+
+        SolveError::PartPanicked { phase, message }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Io { input, message } => write!(f, "Could not read input {}, due to {}", input, message),
+            SolveError::Parse { line, column, message } => write!(f, "Could not parse input at line {}, column {}: {}", line, column, message),
+            SolveError::DanglingInput { line, column, offending_line } =>
+                write!(f, "Dangling input from line {}, column {}: '{}'", line, column, offending_line),
+            SolveError::PartPanicked { phase, message } => write!(f, "{} panicked: {}", phase, message),
+            SolveError::Timeout { limit } => write!(f, "did not finish within {:?}", limit),
+        }
+    }
+}
+
+impl From<GridError> for SolveError {
+    fn from(error: GridError) -> Self {
+        let (line, column) = match error {
+            GridError::Empty { row } => (row, 1),
+            GridError::Ragged { row, expected, found } => (row, expected.min(found) + 1),
+        };
+        SolveError::Parse { line, column, message: error.to_string() }
+    }
+}
+
+// 1-based line and column at which `rest`, a suffix of `input`, starts
+pub fn position(input: &str, rest: &str) -> (usize, usize) {
+    let mut offset = input.len().saturating_sub(rest.len());
+    while !input.is_char_boundary(offset) {
+        offset -= 1
+    }
+    let consumed = &input[..offset];
+    let line = consumed.matches('\n').count() + 1;
+    let line_start = consumed.rfind('\n').map_or(0, |newline| newline + 1);
+
+    (line, consumed[line_start..].chars().count() + 1)
+}
+
+#[derive(Debug, Default)]
+pub struct ParseContext {
+    pub strict: bool,
+    pub warnings: Vec<SolveError>,
+}
@@ -0,0 +1,138 @@
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli::FetchOptions;
+use crate::input;
+
+const USER_AGENT: &str = "aoc2023 input fetcher";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+enum Failure {
+    // the server asked us to slow down, no point in trying the remaining days
+    RateLimited(Option<Duration>),
+    Other(String),
+}
+
+struct Fetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    year: u16,
+    session: String,
+    delay: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Fetcher {
+    // waits until at least `delay` has passed since the previous request
+    fn throttle(&mut self) {
+        if let Some(last_request) = self.last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.delay {
+                thread::sleep(self.delay - elapsed)
+            }
+        }
+        self.last_request = Some(Instant::now())
+    }
+
+    fn download(&mut self, day: usize) -> Result<String, Failure> {
+        self.throttle();
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        let response = self.agent.get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        match response {
+            Ok(response) => response.into_string().map_err(|error| Failure::Other(error.to_string())),
+            Err(ureq::Error::Status(429, response)) => {
+                let retry_after = response.header("Retry-After")
+                    .and_then(|seconds| seconds.trim().parse().ok())
+                    .map(Duration::from_secs);
+                Err(Failure::RateLimited(retry_after))
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(Failure::Other(format!("{} answered {}: {}", url, status, body.trim())))
+            }
+            Err(error) => Err(Failure::Other(error.to_string()))
+        }
+    }
+
+    // a rate limited request is retried once, after the time the server asked for
+    fn fetch(&mut self, day: usize) -> Result<String, Failure> {
+        match self.download(day) {
+            Err(Failure::RateLimited(retry_after)) => {
+                let wait = retry_after.unwrap_or(self.delay).max(self.delay);
+                eprintln!("Rate limited, waiting {:?} before retrying day{}", wait, day);
+                thread::sleep(wait);
+                self.last_request = None;
+                self.download(day)
+            }
+            result => result
+        }
+    }
+}
+
+// written under a temporary name first, so an interrupted download never looks like a cached input. The name starts
+// with a dot, as a `dayN.partial` would be run as a variant of the input
+fn store(path: &Path, contents: &str) -> std::io::Result<()> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let partial = path.with_file_name(format!(".{}.partial", file_name));
+    std::fs::write(&partial, contents)?;
+    std::fs::rename(partial, path)
+}
+
+// downloads every selected day that is not cached yet and returns the exit code
+pub fn run(options: FetchOptions) -> i32 {
+    let Some(session) = options.session else {
+        eprintln!("No session token, pass --session or set AOC_SESSION");
+        return 2;
+    };
+    let directory = input::year_directory(&options.directory, options.year);
+    if let Err(error) = std::fs::create_dir_all(&directory) {
+        eprintln!("Could not create {}, due to {}", directory.display(), error);
+        return 2;
+    }
+
+    let mut fetcher = Fetcher {
+        agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).timeout(TIMEOUT).build(),
+        base_url: options.base_url,
+        year: options.year,
+        session,
+        delay: options.delay,
+        last_request: None,
+    };
+    let mut failures = 0;
+
+    for day in options.days {
+        let path = directory.join(format!("day{}", day));
+        if path.exists() {
+            println!("Cached  day{:<2} - {}", day, path.display());
+            continue;
+        }
+
+        match fetcher.fetch(day) {
+            Ok(contents) => match store(&path, &contents) {
+                Ok(()) => println!("Fetched day{:<2} - {} ({} bytes)", day, path.display(), contents.len()),
+                Err(error) => {
+                    eprintln!("Failed  day{:<2} - could not write {}, due to {}", day, path.display(), error);
+                    failures += 1;
+                }
+            },
+            Err(Failure::Other(message)) => {
+                eprintln!("Failed  day{:<2} - {}", day, message);
+                failures += 1;
+            }
+            Err(Failure::RateLimited(_)) => {
+                eprintln!("Failed  day{:<2} - still rate limited, stopping", day);
+                return 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        1
+    } else {
+        0
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::cli::DEFAULT_YEAR;
+
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => path.display().fmt(f),
+            InputSource::Stdin => f.write_str("<stdin>"),
+        }
+    }
+}
+
+impl InputSource {
+    pub fn key(&self) -> Option<String> {
+        match self {
+            InputSource::File(path) => Some(path.display().to_string()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => std::fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    directory: PathBuf,
+    overrides: HashMap<String, InputSource>,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self { directory: PathBuf::from("inputfiles"), overrides: HashMap::new() }
+    }
+}
+
+impl Inputs {
+    pub fn set_directory(&mut self, directory: PathBuf) {
+        self.directory = directory
+    }
+
+    pub fn select_year(&mut self, year: u16) {
+        self.directory = year_directory(&self.directory, year)
+    }
+
+    pub fn set_override(&mut self, day: usize, source: InputSource) {
+        self.overrides.insert(format!("day{}", day), source);
+    }
+
+    pub fn reads_stdin(&self) -> bool {
+        self.overrides.values().any(|source| matches!(source, InputSource::Stdin))
+    }
+
+    // the input `dayN` and every variant of it next to it, e.g. `day5.example1` or `day5.alice`, sorted by variant.
+    // An overridden day only reads its override
+    pub fn sources_for(&self, filename: &str) -> Vec<(Option<String>, InputSource)> {
+        if let Some(source) = self.overrides.get(filename) {
+            return vec![(None, source.clone())];
+        }
+
+        let prefix = format!("{}.", filename);
+        let mut sources: Vec<_> = std::fs::read_dir(&self.directory).into_iter().flatten().flatten()
+            .filter_map(|entry| {
+                let variant = entry.file_name().to_str()?.strip_prefix(&prefix)?.to_string();
+                (!variant.is_empty()).then(|| (Some(variant), InputSource::File(entry.path())))
+            })
+            .collect();
+        sources.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let main = self.directory.join(filename);
+        if main.exists() || sources.is_empty() {
+            sources.insert(0, (None, InputSource::File(main)));
+        }
+        sources
+    }
+
+    pub fn source_for(&self, filename: &str) -> InputSource {
+        self.overrides.get(filename)
+            .cloned()
+            .unwrap_or_else(|| InputSource::File(self.directory.join(filename)))
+    }
+}
+
+// every year has a directory of its own, e.g. `inputfiles/2022`. Inputs of the default year may also be directly in
+// `inputfiles`, as they were before other years were supported
+pub fn year_directory(directory: &Path, year: u16) -> PathBuf {
+    let year_directory = directory.join(year.to_string());
+    if year == DEFAULT_YEAR && !year_directory.is_dir() {
+        directory.to_path_buf()
+    } else {
+        year_directory
+    }
+}
+
+// CRLF line endings become LF, a byte order mark is dropped, every line loses its trailing whitespace and the input
+// ends in exactly one newline, as downloaded inputs do
+pub fn normalise(input: String) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(&input);
+    let mut normalised = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        normalised.push_str(line.trim_end());
+        normalised.push('\n');
+    }
+
+    normalised.truncate(normalised.trim_end().len());
+    if !normalised.is_empty() {
+        normalised.push('\n');
+    }
+    normalised
+}
@@ -0,0 +1,333 @@
+extern crate core;
+
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use crate::allocations::Allocations;
+use crate::answers::{Answers, Check};
+use crate::baseline::Baseline;
+use crate::cli::{Options, PartSelection};
+use crate::error::{ParseContext, SolveError};
+use crate::input::{InputSource, Inputs};
+use crate::output::{DayRecord, Format, Status, Summary};
+use crate::puzzle::Puzzle;
+use crate::report::ReportFormat;
+
+#[derive(Debug, Default, Clone)]
+pub struct Settings {
+    year: u16,
+    parts: PartSelection,
+    inputs: Inputs,
+    format: Format,
+    bench_iterations: Option<usize>,
+    day_budget: Duration,
+    total_budget: Duration,
+    strict: bool,
+    timeout: Option<Duration>,
+    raw_input: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Context {
+    settings: Settings,
+    pending: Vec<DayRecord>,
+    answers: Answers,
+    failed_checks: usize,
+    records: Vec<DayRecord>,
+    failures: Vec<(String, SolveError)>,
+    threads: usize,
+    wall_clock: Duration,
+    total_duration: Duration,
+    non_parse_duration: Duration,
+    longest: Option<Duration>,
+}
+
+impl Context {
+    fn for_worker(&self) -> Self {
+        Self { settings: self.settings.clone(), ..Self::default() }
+    }
+
+    fn check_answer(&mut self, input: &Option<String>, part: usize, answer: &Option<String>) -> Option<Check> {
+        let check = self.answers.check(input.as_ref()?, part, answer.as_ref()?);
+        if matches!(check, Check::Fail { .. }) {
+            self.failed_checks += 1;
+        }
+        Some(check)
+    }
+
+    fn report_pending(&mut self) {
+        for record in std::mem::take(&mut self.pending) {
+            self.report(record)
+        }
+    }
+
+    fn report(&mut self, mut record: DayRecord) {
+        record.part1_check = self.check_answer(&record.input, 1, &record.part1);
+        record.part2_check = self.check_answer(&record.input, 2, &record.part2);
+
+        if let Some(error) = &record.error {
+            self.failures.push((record.label(), error.clone()));
+        }
+
+        // variants like examples are checked, but only the main inputs count towards the total
+        if record.status() == Status::Solved && record.variant.is_none() {
+            let this_task = record.total();
+
+            self.longest = Some(self.longest.unwrap_or(this_task).max(this_task));
+            self.total_duration += this_task;
+            self.non_parse_duration += record.non_parse();
+        }
+
+        let line = self.settings.format.day(&record, self.settings.total_budget);
+        if record.error.is_some() && self.settings.format == Format::Text {
+            eprintln!("{}", line)
+        } else {
+            println!("{}", line)
+        }
+        self.records.push(record)
+    }
+
+    fn summary(&self) -> Summary {
+        Summary {
+            year: self.settings.year,
+            total: self.total_duration,
+            non_parse: self.non_parse_duration,
+            longest: self.longest,
+            bench_iterations: self.settings.bench_iterations,
+            budget: self.settings.total_budget,
+            wall_clock: self.wall_clock,
+            threads: self.threads,
+            failures: self.failures.clone(),
+        }
+    }
+}
+
+
+// the object safe side of a Puzzle, so days with different input and answer types can share a registry
+pub trait Solver: Sync {
+    fn solve(&self, context: &mut Context, name: &str);
+}
+
+impl<P: Puzzle + Sync> Solver for P {
+    fn solve(&self, context: &mut Context, name: &str) {
+        solve(context, name, self)
+    }
+}
+
+fn run_part<Answer: Display>(
+    phase: &'static str,
+    part: impl FnOnce() -> Answer,
+) -> Result<String, SolveError> {
+    panic::catch_unwind(AssertUnwindSafe(|| part().to_string()))
+        .map_err(|payload| SolveError::panicked(phase, payload))
+}
+
+fn solve<P: Puzzle>(context: &mut Context, filename: &str, puzzle: &P) {
+    for (variant, path) in context.settings.inputs.sources_for(filename) {
+        solve_input(context, filename, variant, path, puzzle)
+    }
+}
+
+fn solve_input<P: Puzzle>(context: &mut Context, filename: &str, variant: Option<String>, path: InputSource, puzzle: &P) {
+    let settings = &context.settings;
+    let budget = puzzle.budget().unwrap_or(settings.day_budget);
+    let mut record = DayRecord::new(settings.year, filename, variant, path.key(), budget);
+
+    let contents = match path.read() {
+        Ok(contents) if settings.raw_input || puzzle.raw_input() => contents,
+        Ok(contents) => input::normalise(contents),
+        Err(error) => {
+            record.error = Some(SolveError::Io { input: path.to_string(), message: error.to_string() });
+            context.pending.push(record);
+            return;
+        }
+    };
+    let bench_input = settings.bench_iterations.map(|_| contents.clone());
+    let mut parse_context = ParseContext { strict: puzzle.strict() || settings.strict, ..ParseContext::default() };
+
+    let start = Instant::now();
+    let (parsed, parse_allocations) = allocations::measure(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| puzzle.parse(contents, &mut parse_context)))
+            .unwrap_or_else(|payload| Err(SolveError::panicked("parse", payload)))
+    });
+    let after_parse = Instant::now();
+    record.parse = after_parse - start;
+    record.warnings = parse_context.warnings;
+    if allocations::enabled() {
+        record.allocations = Some(Allocations { parse: parse_allocations, part1: None, part2: None });
+    }
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            record.error = Some(error);
+            context.pending.push(record);
+            return;
+        }
+    };
+
+    let solution_part1 = settings.parts.includes_part1()
+        .then(|| allocations::measure(|| run_part("part 1", || puzzle.part1(&parsed))));
+    let after_p1 = Instant::now();
+    let solution_part2 = settings.parts.includes_part2()
+        .then(|| allocations::measure(|| run_part("part 2", || puzzle.part2(&parsed))));
+    let after_p2 = Instant::now();
+    record.part1_duration = after_p1 - after_parse;
+    record.part2_duration = after_p2 - after_p1;
+
+    if let Some(allocations) = &mut record.allocations {
+        allocations.part1 = solution_part1.as_ref().map(|(_, stats)| *stats);
+        allocations.part2 = solution_part2.as_ref().map(|(_, stats)| *stats);
+    }
+    let solution_part1 = solution_part1.map(|(solution, _)| solution);
+    let solution_part2 = solution_part2.map(|(solution, _)| solution);
+    for (solution, target) in [(solution_part1, &mut record.part1), (solution_part2, &mut record.part2)] {
+        match solution {
+            Some(Ok(solution)) => *target = Some(solution),
+            Some(Err(error)) => { record.error.get_or_insert(error); }
+            None => {}
+        }
+    }
+
+    if let (Some(iterations), Some(input), None) = (settings.bench_iterations, bench_input, &record.error) {
+        drop(parsed);
+        record.bench = bench::run(iterations, settings.parts, &input, puzzle);
+        if let Some(bench) = &record.bench {
+            record.parse = bench.parse.median;
+            record.part1_duration = bench.part1.map(|stats| stats.median).unwrap_or_default();
+            record.part2_duration = bench.part2.map(|stats| stats.median).unwrap_or_default();
+        }
+    }
+
+    context.pending.push(record)
+}
+
+pub mod allocations;
+mod answers;
+mod baseline;
+mod bench;
+pub mod cli;
+pub mod error;
+pub mod fetch;
+mod input;
+mod output;
+mod report;
+pub mod puzzle;
+mod runner;
+pub mod util;
+mod watch;
+
+pub mod y2023;
+
+// the days that have a solution, by year and day. The remaining ones are skipped
+pub const REGISTRY: &[(u16, usize, &dyn Solver)] = &[
+    (2023, 1, &y2023::day1::Day1), (2023, 2, &y2023::day2::Day2), (2023, 3, &y2023::day3::Day3),
+    (2023, 4, &y2023::day4::Day4), (2023, 5, &y2023::day5::Day5), (2023, 6, &y2023::day6::Day6),
+    (2023, 7, &y2023::day7::Day7), (2023, 8, &y2023::day8::Day8), (2023, 9, &y2023::day9::Day9),
+    (2023, 10, &y2023::day10::Day10), (2023, 11, &y2023::day11::Day11), (2023, 12, &y2023::day12::Day12),
+    (2023, 13, &y2023::day13::Day13), (2023, 14, &y2023::day14::Day14), (2023, 15, &y2023::day15::Day15),
+    (2023, 16, &y2023::day16::Day16), (2023, 17, &y2023::day17::Day17), (2023, 18, &y2023::day18::Day18),
+    (2023, 19, &y2023::day19::Day19), (2023, 20, &y2023::day20::Day20), (2023, 21, &y2023::day21::Day21),
+];
+
+pub fn solver_for(year: u16, day: usize) -> Option<&'static dyn Solver> {
+    REGISTRY.iter()
+        .find(|&&(registered_year, registered_day, _)| registered_year == year && registered_day == day)
+        .map(|&(_, _, solver)| solver)
+}
+
+// runs the selected days and returns the exit code
+pub fn run(mut options: Options) -> i32 {
+    if !REGISTRY.iter().any(|&(year, _, _)| year == options.year) {
+        eprintln!("There are no solutions for {}", options.year);
+        return 2;
+    }
+    options.inputs.select_year(options.year);
+
+    let compare_to = match options.compare.as_ref().map(|path| Baseline::load(path)).transpose() {
+        Ok(baseline) => baseline,
+        Err(error) => {
+            eprintln!("{}", error);
+            return 2
+        }
+    };
+
+    let answers = match Answers::load(&options.answers, options.record) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("{}", error);
+            return 2
+        }
+    };
+
+    let settings = Settings {
+        year: options.year,
+        parts: options.parts,
+        inputs: options.inputs,
+        format: options.format,
+        bench_iterations: options.bench,
+        day_budget: options.day_budget,
+        total_budget: options.budget,
+        strict: options.strict,
+        timeout: options.timeout,
+        raw_input: options.raw_input,
+    };
+    if options.allocations {
+        allocations::enable()
+    }
+    let mut context = Context { settings, answers, ..Context::default() };
+    let format = context.settings.format;
+
+    if let Some(header) = format.header() {
+        println!("{}", header)
+    }
+    if let Some(day) = options.watch {
+        let Some(solver) = solver_for(options.year, day) else {
+            eprintln!("day{} of {} has no solution to watch", day, options.year);
+            return 2;
+        };
+        return watch::run(&mut context, day, solver, options.example);
+    }
+    let days: Vec<_> = options.days.iter()
+        .filter_map(|&day| Some((format!("day{}", day), solver_for(options.year, day)?)))
+        .collect();
+    match options.threads {
+        Some(threads) => runner::run_parallel(&mut context, &days, threads),
+        None => runner::run_sequential(&mut context, &days)
+    }
+    println!("{}", format.summary(&context.summary()));
+
+    if let Err(error) = context.answers.save() {
+        eprintln!("Could not record answers, due to {}", error)
+    }
+    if let Some(path) = &options.report {
+        let report = report::render(ReportFormat::for_path(path), &context.records, &context.summary());
+        if let Err(error) = std::fs::write(path, report) {
+            eprintln!("Could not write report {}, due to {}", path.display(), error)
+        }
+    }
+    if let Some(path) = &options.save_baseline {
+        if let Err(error) = Baseline::from_records(&context.records).save(path) {
+            eprintln!("Could not save baseline {}, due to {}", path.display(), error)
+        }
+    }
+
+    let mut regressions = 0;
+    if let Some(baseline) = compare_to {
+        for regression in baseline.regressions(&context.records, options.threshold) {
+            let line = format.regression(&regression, options.threshold);
+            if format == Format::Csv {
+                eprintln!("{}", line)
+            } else {
+                println!("{}", line)
+            }
+            regressions += 1;
+        }
+    }
+
+    if context.failed_checks > 0 || regressions > 0 || !context.failures.is_empty() || context.summary().over_budget() {
+        1
+    } else {
+        0
+    }
+}
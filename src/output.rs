@@ -0,0 +1,493 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::allocations::Allocations;
+use crate::answers::Check;
+use crate::baseline::Regression;
+use crate::bench::{BenchStats, PhaseStats};
+use crate::error::SolveError;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("'{}' is not a known format, expected json, csv or text", s))
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    Solved,
+    InputMissing,
+    ParseFailed,
+    Panicked,
+    TimedOut,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::InputMissing => "input_missing",
+            Status::ParseFailed => "parse_failed",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayRecord {
+    pub year: u16,
+    pub day: String,
+    pub variant: Option<String>,
+    pub input: Option<String>,
+    pub error: Option<SolveError>,
+    pub warnings: Vec<SolveError>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub part1_check: Option<Check>,
+    pub part2_check: Option<Check>,
+    pub parse: Duration,
+    pub part1_duration: Duration,
+    pub part2_duration: Duration,
+    pub bench: Option<BenchStats>,
+    pub allocations: Option<Allocations>,
+    pub budget: Duration,
+}
+
+impl DayRecord {
+    pub fn new(year: u16, day: &str, variant: Option<String>, input: Option<String>, budget: Duration) -> Self {
+        Self {
+            year,
+            day: day.to_string(),
+            variant,
+            input,
+            error: None,
+            warnings: Vec::new(),
+            part1: None,
+            part2: None,
+            part1_check: None,
+            part2_check: None,
+            parse: Duration::ZERO,
+            part1_duration: Duration::ZERO,
+            part2_duration: Duration::ZERO,
+            bench: None,
+            allocations: None,
+            budget,
+        }
+    }
+
+    // the day, followed by the variant of its input if it is not the main one, e.g. `day5.example1`
+    pub fn label(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{}.{}", self.day, variant),
+            None => self.day.clone()
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        match &self.error {
+            None => Status::Solved,
+            Some(SolveError::Io { .. }) => Status::InputMissing,
+            Some(SolveError::Parse { .. } | SolveError::DanglingInput { .. }) => Status::ParseFailed,
+            Some(SolveError::PartPanicked { .. }) => Status::Panicked,
+            Some(SolveError::Timeout { .. }) => Status::TimedOut,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.non_parse()
+    }
+
+    pub fn non_parse(&self) -> Duration {
+        self.part1_duration + self.part2_duration
+    }
+
+    pub fn over_budget(&self) -> bool {
+        self.status() == Status::Solved && self.total() > self.budget
+    }
+}
+
+fn percent_of(duration: Duration, budget: Duration) -> f64 {
+    100.0 * duration.as_secs_f64() / budget.as_secs_f64()
+}
+
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub year: u16,
+    pub total: Duration,
+    pub non_parse: Duration,
+    pub longest: Option<Duration>,
+    pub bench_iterations: Option<usize>,
+    pub budget: Duration,
+    pub wall_clock: Duration,
+    pub threads: usize,
+    pub failures: Vec<(String, SolveError)>,
+}
+
+impl Summary {
+    pub fn over_budget(&self) -> bool {
+        self.total > self.budget
+    }
+}
+
+const CSV_HEADER: &str = "kind,year,day,variant,status,part1,part2,part1_check,part2_check,parse_ns,part1_ns,part2_ns,total_ns,non_parse_ns,longest_ns,\
+bench_iterations,parse_min_ns,parse_median_ns,parse_p95_ns,parse_max_ns,part1_min_ns,part1_median_ns,part1_p95_ns,part1_max_ns,\
+part2_min_ns,part2_median_ns,part2_p95_ns,part2_max_ns,budget_ns,budget_percent,over_budget,wall_clock_ns,threads,error,warnings,\
+parse_allocations,parse_allocated_bytes,parse_peak_bytes,part1_allocations,part1_allocated_bytes,part1_peak_bytes,\
+part2_allocations,part2_allocated_bytes,part2_peak_bytes";
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c)
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_optional_string(value: &Option<String>) -> String {
+    value.as_deref().map_or_else(|| "null".to_string(), json_string)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn skipped_or(solution: &Option<String>) -> &str {
+    solution.as_deref().unwrap_or("-")
+}
+
+fn check_name(check: &Option<Check>) -> &'static str {
+    check.as_ref().map_or("", Check::as_str)
+}
+
+fn json_error(error: &SolveError) -> String {
+    format!("{{\"kind\":\"{}\",\"message\":{}}}", error.kind(), json_string(&error.to_string()))
+}
+
+fn json_errors<'a>(errors: impl Iterator<Item=&'a SolveError>) -> String {
+    format!("[{}]", errors.map(json_error).collect::<Vec<_>>().join(","))
+}
+
+fn text_warnings(record: &DayRecord) -> String {
+    record.warnings.iter().map(|warning| format!("\n      {:5} warning: {}", "", warning)).collect()
+}
+
+fn json_check(check: &Option<Check>) -> String {
+    match check {
+        Some(Check::Fail { expected }) => format!("{{\"result\":\"FAIL\",\"expected\":{}}}", json_string(expected)),
+        Some(check) => format!("{{\"result\":\"{}\"}}", check.as_str()),
+        None => "null".to_string()
+    }
+}
+
+fn text_checks(record: &DayRecord) -> String {
+    let mut checks = String::new();
+    for (part, check) in [(1, &record.part1_check), (2, &record.part2_check)] {
+        if let Some(check) = check {
+            checks.push_str(if checks.is_empty() { " --- " } else { ", " });
+            write!(checks, "part{} {}", part, check).unwrap();
+        }
+    }
+    checks
+}
+
+fn text_bench(bench: &BenchStats) -> String {
+    let mut text = format!("\n      {:5} {} runs, min/median/p95/max:", "", bench.iterations);
+    for (name, stats) in bench.phases() {
+        if let Some(stats) = stats {
+            write!(text, " {} {:?}/{:?}/{:?}/{:?}", name, stats.min, stats.median, stats.p95, stats.max).unwrap();
+        }
+    }
+    text
+}
+
+fn json_phase(stats: &Option<PhaseStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "{{\"min_ns\":{},\"median_ns\":{},\"p95_ns\":{},\"max_ns\":{}}}",
+            stats.min.as_nanos(), stats.median.as_nanos(), stats.p95.as_nanos(), stats.max.as_nanos()
+        ),
+        None => "null".to_string()
+    }
+}
+
+fn json_bench(bench: &Option<BenchStats>) -> String {
+    match bench {
+        Some(bench) => format!(
+            "{{\"iterations\":{},\"parse\":{},\"part1\":{},\"part2\":{}}}",
+            bench.iterations,
+            json_phase(&Some(bench.parse)),
+            json_phase(&bench.part1),
+            json_phase(&bench.part2)
+        ),
+        None => "null".to_string()
+    }
+}
+
+fn csv_bench(bench: &Option<BenchStats>, fields: &mut Vec<String>) {
+    let Some(bench) = bench else {
+        fields.resize(fields.len() + 13, String::new());
+        return;
+    };
+
+    fields.push(bench.iterations.to_string());
+    for (_, stats) in bench.phases() {
+        match stats {
+            Some(stats) => fields.extend([stats.min, stats.median, stats.p95, stats.max].map(|d| d.as_nanos().to_string())),
+            None => fields.resize(fields.len() + 4, String::new())
+        }
+    }
+}
+
+fn text_allocations(allocations: &Allocations) -> String {
+    let mut text = format!("\n      {:5} allocations:", "");
+    for (name, stats) in allocations.phases() {
+        if let Some(stats) = stats {
+            write!(text, " {} {} ({} bytes, peak {} bytes)", name, stats.allocations, stats.bytes, stats.peak_bytes).unwrap();
+        }
+    }
+    text
+}
+
+fn json_allocations(allocations: &Option<Allocations>) -> String {
+    let Some(allocations) = allocations else {
+        return "null".to_string();
+    };
+    let phases = allocations.phases().map(|(name, stats)| match stats {
+        Some(stats) => format!(
+            "\"{}\":{{\"allocations\":{},\"bytes\":{},\"peak_bytes\":{}}}",
+            name, stats.allocations, stats.bytes, stats.peak_bytes
+        ),
+        None => format!("\"{}\":null", name)
+    });
+    format!("{{{}}}", phases.join(","))
+}
+
+fn csv_allocations(allocations: &Option<Allocations>, fields: &mut Vec<String>) {
+    let Some(allocations) = allocations else {
+        fields.resize(fields.len() + 9, String::new());
+        return;
+    };
+
+    for (_, stats) in allocations.phases() {
+        match stats {
+            Some(stats) => fields.extend([stats.allocations, stats.bytes, stats.peak_bytes].map(|n| n.to_string())),
+            None => fields.resize(fields.len() + 3, String::new())
+        }
+    }
+}
+
+fn text_budget(record: &DayRecord) -> String {
+    if record.over_budget() {
+        format!(" --- OVER its {:?} budget", record.budget)
+    } else {
+        String::new()
+    }
+}
+
+impl Format {
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Format::Csv => Some(CSV_HEADER),
+            _ => None
+        }
+    }
+
+    pub fn day(self, record: &DayRecord, total_budget: Duration) -> String {
+        let budget_percent = percent_of(record.total(), total_budget);
+        match self {
+            Format::Text => {
+                let mut text = if let Some(error) = &record.error {
+                    let outcome = match record.status() {
+                        Status::Panicked => "PANIC",
+                        Status::TimedOut => "TIMEOUT",
+                        _ => "Failed"
+                    };
+                    format!("{} {:5} - {}", outcome, record.label(), error)
+                } else {
+                    format!(
+                        "Solved {:5} - part1: {:>16}, part2: {:>16} --- ({:10?} parse, {:10?} part 1, {:10?} part 2, {:5.1}% of budget){}{}",
+                        record.label(),
+                        skipped_or(&record.part1),
+                        skipped_or(&record.part2),
+                        record.parse,
+                        record.part1_duration,
+                        record.part2_duration,
+                        budget_percent,
+                        text_budget(record),
+                        text_checks(record)
+                    )
+                };
+                if let Some(bench) = &record.bench {
+                    text.push_str(&text_bench(bench));
+                }
+                if let Some(allocations) = &record.allocations {
+                    text.push_str(&text_allocations(allocations));
+                }
+                text.push_str(&text_warnings(record));
+                text
+            }
+            Format::Json => format!(
+                "{{\"kind\":\"day\",\"year\":{},\"day\":{},\"variant\":{},\"status\":\"{}\",\"error\":{},\"warnings\":{},\"part1\":{},\"part2\":{},\"part1_check\":{},\"part2_check\":{},\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{},\"bench\":{},\"allocations\":{},\"budget_ns\":{},\"budget_percent\":{:.3},\"over_budget\":{}}}",
+                record.year,
+                json_string(&record.day),
+                json_optional_string(&record.variant),
+                record.status().as_str(),
+                record.error.as_ref().map_or_else(|| "null".to_string(), json_error),
+                json_errors(record.warnings.iter()),
+                json_optional_string(&record.part1),
+                json_optional_string(&record.part2),
+                json_check(&record.part1_check),
+                json_check(&record.part2_check),
+                record.parse.as_nanos(),
+                record.part1_duration.as_nanos(),
+                record.part2_duration.as_nanos(),
+                json_bench(&record.bench),
+                json_allocations(&record.allocations),
+                record.budget.as_nanos(),
+                budget_percent,
+                record.over_budget()
+            ),
+            Format::Csv => {
+                let mut fields = vec![
+                    "day".to_string(),
+                    record.year.to_string(),
+                    csv_field(&record.day),
+                    csv_field(record.variant.as_deref().unwrap_or("")),
+                    record.status().as_str().to_string(),
+                    csv_field(record.part1.as_deref().unwrap_or("")),
+                    csv_field(record.part2.as_deref().unwrap_or("")),
+                    check_name(&record.part1_check).to_string(),
+                    check_name(&record.part2_check).to_string(),
+                    record.parse.as_nanos().to_string(),
+                    record.part1_duration.as_nanos().to_string(),
+                    record.part2_duration.as_nanos().to_string(),
+                    record.total().as_nanos().to_string(),
+                    record.non_parse().as_nanos().to_string(),
+                    String::new(),
+                ];
+                csv_bench(&record.bench, &mut fields);
+                fields.extend([record.budget.as_nanos().to_string(), format!("{:.3}", budget_percent), record.over_budget().to_string()]);
+                fields.resize(fields.len() + 2, String::new());
+                fields.push(csv_field(&record.error.as_ref().map(ToString::to_string).unwrap_or_default()));
+                fields.push(csv_field(&record.warnings.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")));
+                csv_allocations(&record.allocations, &mut fields);
+                fields.join(",")
+            }
+        }
+    }
+
+    pub fn regression(self, regression: &Regression, threshold_percent: f64) -> String {
+        match self {
+            Format::Json => format!(
+                "{{\"kind\":\"regression\",\"day\":{},\"phase\":\"{}\",\"baseline_ns\":{},\"current_ns\":{},\"percent_slower\":{:.1},\"threshold_percent\":{}}}",
+                json_string(&regression.day),
+                regression.phase,
+                regression.baseline.as_nanos(),
+                regression.current.as_nanos(),
+                regression.percent_slower(),
+                threshold_percent
+            ),
+            _ => format!(
+                "Regression in {} {}: {:?} -> {:?} ({:+.1}%, threshold {}%)",
+                regression.day,
+                regression.phase,
+                regression.baseline,
+                regression.current,
+                regression.percent_slower(),
+                threshold_percent
+            )
+        }
+    }
+
+    pub fn summary(self, summary: &Summary) -> String {
+        let longest = summary.longest.unwrap_or_default();
+        let budget_percent = percent_of(summary.total, summary.budget);
+        match self {
+            Format::Text => {
+                let mut text = format!(
+                    "AoC so far, excluding io: {:?} total, {:?} without overhead. Longest day runtime was {:?} ",
+                    summary.total, summary.non_parse, longest
+                );
+                if let Some(iterations) = summary.bench_iterations {
+                    write!(text, "\nThese are the medians over {} runs.", iterations).unwrap();
+                }
+                write!(text, "\nThat is {:.1}% of the {:?} budget", budget_percent, summary.budget).unwrap();
+                if summary.over_budget() {
+                    text.push_str(" - OVER BUDGET");
+                }
+                write!(text, "\nWall clock time, including io, was {:?} on {} thread(s)", summary.wall_clock, summary.threads).unwrap();
+                if !summary.failures.is_empty() {
+                    write!(text, "\n{} day(s) failed:", summary.failures.len()).unwrap();
+                    for (day, error) in &summary.failures {
+                        write!(text, "\n    {:5} - {}", day, error).unwrap();
+                    }
+                }
+                text
+            }
+            Format::Json => format!(
+                "{{\"kind\":\"summary\",\"year\":{},\"total_ns\":{},\"non_parse_ns\":{},\"longest_ns\":{},\"bench_iterations\":{},\"budget_ns\":{},\"budget_percent\":{:.3},\"over_budget\":{},\"wall_clock_ns\":{},\"threads\":{},\"failures\":[{}]}}",
+                summary.year,
+                summary.total.as_nanos(),
+                summary.non_parse.as_nanos(),
+                longest.as_nanos(),
+                summary.bench_iterations.map_or_else(|| "null".to_string(), |iterations| iterations.to_string()),
+                summary.budget.as_nanos(),
+                budget_percent,
+                summary.over_budget(),
+                summary.wall_clock.as_nanos(),
+                summary.threads,
+                summary.failures.iter()
+                    .map(|(day, error)| format!("{{\"day\":{},\"error\":{}}}", json_string(day), json_error(error)))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            Format::Csv => {
+                let mut fields = vec![String::new(); 16];
+                fields[0] = "summary".to_string();
+                fields[1] = summary.year.to_string();
+                fields[9] = (summary.total - summary.non_parse).as_nanos().to_string();
+                fields[12] = summary.total.as_nanos().to_string();
+                fields[13] = summary.non_parse.as_nanos().to_string();
+                fields[14] = longest.as_nanos().to_string();
+                fields[15] = summary.bench_iterations.map(|iterations| iterations.to_string()).unwrap_or_default();
+                fields.resize(fields.len() + 12, String::new());
+                fields.extend([summary.budget.as_nanos().to_string(), format!("{:.3}", budget_percent), summary.over_budget().to_string()]);
+                fields.extend([summary.wall_clock.as_nanos().to_string(), summary.threads.to_string()]);
+                fields.push(csv_field(&summary.failures.iter().map(|(day, _)| day.as_str()).collect::<Vec<_>>().join(" ")));
+                fields.resize(fields.len() + 10, String::new());
+                fields.join(",")
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use nom::IResult;
+
+use crate::error::{self, ParseContext, SolveError};
+
+pub trait Puzzle {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError>;
+    fn part1(&self, input: &Self::Input) -> Self::Answer1;
+    fn part2(&self, input: &Self::Input) -> Self::Answer2;
+
+    // overrides the default day budget given on the command line
+    fn budget(&self) -> Option<Duration> {
+        None
+    }
+
+    // fail on dangling input even without --strict
+    fn strict(&self) -> bool {
+        false
+    }
+
+    // hand the input over exactly as read, even without --raw-input
+    fn raw_input(&self) -> bool {
+        false
+    }
+}
+
+pub fn nom_parse<
+    Parsed,
+    Parser: FnMut(&str) -> IResult<&str, Parsed>
+>(input: &str, mut p: Parser, context: &mut ParseContext) -> Result<Parsed, SolveError> {
+    match p(input) {
+        Ok((rest, parsed)) => {
+            let dangling = rest.trim_start();
+            if !dangling.is_empty() {
+                let (line, column) = error::position(input, dangling);
+                let offending_line = input.lines().nth(line - 1).unwrap_or_default().to_string();
+                let error = SolveError::DanglingInput { line, column, offending_line };
+                if context.strict {
+                    return Err(error);
+                }
+                context.warnings.push(error)
+            }
+            Ok(parsed)
+        }
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            let (line, column) = error::position(input, error.input);
+            Err(SolveError::Parse { line, column, message: format!("{:?} did not match", error.code) })
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            let (line, column) = error::position(input, "");
+            Err(SolveError::Parse { line, column, message: "unexpected end of input".to_string() })
+        }
+    }
+}
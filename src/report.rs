@@ -0,0 +1,191 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::output::{DayRecord, Status, Summary};
+
+const BAR_WIDTH: usize = 40;
+const MARKDOWN_BARS: [char; 3] = ['▓', '█', '░'];
+const HTML_COLOURS: [&str; 3] = ["#8da0cb", "#fc8d62", "#66c2a5"];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("html" | "htm") => ReportFormat::Html,
+            _ => ReportFormat::Markdown
+        }
+    }
+}
+
+fn phases(record: &DayRecord) -> [Duration; 3] {
+    [record.parse, record.part1_duration, record.part2_duration]
+}
+
+fn percent_of(duration: Duration, of: Duration) -> f64 {
+    if of.is_zero() {
+        0.0
+    } else {
+        100.0 * duration.as_secs_f64() / of.as_secs_f64()
+    }
+}
+
+fn html_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn markdown_escape(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+// the bar of every day is scaled to the slowest day, so the fast ones are still visible
+fn markdown_bar(record: &DayRecord, scale: Duration) -> String {
+    let mut bar = String::new();
+    for (duration, symbol) in phases(record).into_iter().zip(MARKDOWN_BARS) {
+        let width = (percent_of(duration, scale) * BAR_WIDTH as f64 / 100.0).round() as usize;
+        bar.extend(std::iter::repeat_n(symbol, width));
+    }
+    if bar.is_empty() {
+        bar
+    } else {
+        format!("`{}`", bar)
+    }
+}
+
+fn markdown(records: &[DayRecord], summary: &Summary, scale: Duration) -> String {
+    let mut report = format!("# Advent of Code {} performance\n\n", summary.year);
+    writeln!(report, "Bars: {} parse, {} part 1, {} part 2, scaled to the slowest day.\n", MARKDOWN_BARS[0], MARKDOWN_BARS[1], MARKDOWN_BARS[2]).unwrap();
+    report.push_str("| Day | Status | Part 1 | Part 2 | Parse | Part 1 time | Part 2 time | Total | Share of budget | |\n");
+    report.push_str("|-----|--------|--------|--------|------:|------------:|------------:|------:|----------------:|-|\n");
+
+    for record in records {
+        if let Some(error) = &record.error {
+            writeln!(report, "| {} | {} | {} | | | | | | | |", record.label(), record.status().as_str(), markdown_escape(&error.to_string())).unwrap();
+            continue;
+        }
+        writeln!(
+            report,
+            "| {} | {} | {} | {} | {:?} | {:?} | {:?} | {:?} | {:.1}% | {} |",
+            record.label(),
+            record.status().as_str(),
+            markdown_escape(record.part1.as_deref().unwrap_or("-")),
+            markdown_escape(record.part2.as_deref().unwrap_or("-")),
+            record.parse,
+            record.part1_duration,
+            record.part2_duration,
+            record.total(),
+            percent_of(record.total(), summary.budget),
+            markdown_bar(record, scale)
+        ).unwrap();
+    }
+
+    let filled = ((percent_of(summary.total, summary.budget) * BAR_WIDTH as f64 / 100.0).round() as usize).min(BAR_WIDTH);
+    writeln!(
+        report,
+        "\n**Total: {:?} of the {:?} goal ({:.1}%){}**\n\n`{}{}`",
+        summary.total,
+        summary.budget,
+        percent_of(summary.total, summary.budget),
+        if summary.over_budget() { " - OVER BUDGET" } else { "" },
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled)
+    ).unwrap();
+    if let Some(iterations) = summary.bench_iterations {
+        writeln!(report, "\nTimings are the medians over {} runs.", iterations).unwrap();
+    }
+    report
+}
+
+fn html_bar(record: &DayRecord, scale: Duration) -> String {
+    let mut bar = String::from("<div class=\"bar\">");
+    for ((duration, colour), name) in phases(record).into_iter().zip(HTML_COLOURS).zip(["parse", "part 1", "part 2"]) {
+        write!(
+            bar,
+            "<span style=\"width:{:.2}%;background:{}\" title=\"{} {:?}\"></span>",
+            percent_of(duration, scale), colour, name, duration
+        ).unwrap();
+    }
+    bar.push_str("</div>");
+    bar
+}
+
+fn html(records: &[DayRecord], summary: &Summary, scale: Duration) -> String {
+    let mut report = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code {} performance</title>\n<style>\n", summary.year);
+    report.push_str("body { font-family: sans-serif; margin: 2em; }\n");
+    report.push_str("table { border-collapse: collapse; }\n");
+    report.push_str("th, td { padding: 0.2em 0.6em; border-bottom: 1px solid #ddd; text-align: right; }\n");
+    report.push_str("td.text, th.text { text-align: left; }\n");
+    report.push_str(".bar { display: flex; width: 20em; height: 1em; background: #f4f4f4; }\n");
+    report.push_str(".total { width: 40em; }\n");
+    report.push_str(".failed { color: #c00; }\n");
+    report.push_str(".legend span { display: inline-block; width: 1em; height: 1em; margin: 0 0.3em 0 1em; vertical-align: middle; }\n");
+    write!(report, "</style>\n</head>\n<body>\n<h1>Advent of Code {} performance</h1>\n<p class=\"legend\">", summary.year).unwrap();
+    for (colour, name) in HTML_COLOURS.into_iter().zip(["parse", "part 1", "part 2"]) {
+        write!(report, "<span style=\"background:{}\"></span>{}", colour, name).unwrap();
+    }
+    report.push_str(", scaled to the slowest day</p>\n<table>\n");
+    report.push_str("<tr><th class=\"text\">Day</th><th class=\"text\">Status</th><th class=\"text\">Part 1</th><th class=\"text\">Part 2</th>\
+<th>Parse</th><th>Part 1 time</th><th>Part 2 time</th><th>Total</th><th>Share of budget</th><th></th></tr>\n");
+
+    for record in records {
+        if let Some(error) = &record.error {
+            writeln!(
+                report,
+                "<tr class=\"failed\"><td class=\"text\">{}</td><td class=\"text\">{}</td><td class=\"text\" colspan=\"8\">{}</td></tr>",
+                html_escape(&record.label()), record.status().as_str(), html_escape(&error.to_string())
+            ).unwrap();
+            continue;
+        }
+        writeln!(
+            report,
+            "<tr><td class=\"text\">{}</td><td class=\"text\">{}</td><td class=\"text\">{}</td><td class=\"text\">{}</td>\
+<td>{:?}</td><td>{:?}</td><td>{:?}</td><td>{:?}</td><td>{:.1}%</td><td>{}</td></tr>",
+            html_escape(&record.label()),
+            record.status().as_str(),
+            html_escape(record.part1.as_deref().unwrap_or("-")),
+            html_escape(record.part2.as_deref().unwrap_or("-")),
+            record.parse,
+            record.part1_duration,
+            record.part2_duration,
+            record.total(),
+            percent_of(record.total(), summary.budget),
+            html_bar(record, scale)
+        ).unwrap();
+    }
+    report.push_str("</table>\n");
+
+    let percent = percent_of(summary.total, summary.budget);
+    writeln!(
+        report,
+        "<h2>Total: {:?} of the {:?} goal ({:.1}%){}</h2>\n<div class=\"bar total\"><span style=\"width:{:.2}%;background:{}\"></span></div>",
+        summary.total,
+        summary.budget,
+        percent,
+        if summary.over_budget() { " - OVER BUDGET" } else { "" },
+        percent.min(100.0),
+        if summary.over_budget() { "#c00" } else { HTML_COLOURS[2] }
+    ).unwrap();
+    if let Some(iterations) = summary.bench_iterations {
+        writeln!(report, "<p>Timings are the medians over {} runs.</p>", iterations).unwrap();
+    }
+    report.push_str("</body>\n</html>\n");
+    report
+}
+
+pub fn render(format: ReportFormat, records: &[DayRecord], summary: &Summary) -> String {
+    let scale = records.iter()
+        .filter(|record| record.status() == Status::Solved)
+        .map(DayRecord::total)
+        .max()
+        .unwrap_or_default();
+
+    match format {
+        ReportFormat::Html => html(records, summary, scale),
+        ReportFormat::Markdown => markdown(records, summary, scale),
+    }
+}
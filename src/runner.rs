@@ -0,0 +1,103 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Instant;
+
+use crate::error::SolveError;
+use crate::output::DayRecord;
+use crate::{Context, Solver};
+
+pub type Day = (String, &'static dyn Solver);
+
+fn failed(context: &mut Context, name: &str, error: SolveError) {
+    let settings = &context.settings;
+    let mut record = DayRecord::new(settings.year, name, None, settings.inputs.source_for(name).key(), settings.day_budget);
+    record.error = Some(error);
+    context.pending.push(record)
+}
+
+// panics outside of the parse and the parts still only fail this day. With a timeout the day runs on a thread of
+// its own, which is abandoned when it does not finish in time
+pub fn run_day(context: &mut Context, name: &str, solver: &'static dyn Solver) {
+    let Some(timeout) = context.settings.timeout else {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| solver.solve(context, name))) {
+            context.pending.clear();
+            failed(context, name, SolveError::panicked("day", payload))
+        }
+        return;
+    };
+
+    let mut worker = context.for_worker();
+    let (sender, receiver) = mpsc::channel();
+    let day = name.to_string();
+    let handle = thread::spawn(move || {
+        solver.solve(&mut worker, &day);
+        let _ = sender.send(worker.pending);
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(records) => context.pending.extend(records),
+        Err(RecvTimeoutError::Timeout) => failed(context, name, SolveError::Timeout { limit: timeout }),
+        Err(RecvTimeoutError::Disconnected) => {
+            let error = match handle.join() {
+                Err(payload) => SolveError::panicked("day", payload),
+                Ok(()) => SolveError::PartPanicked { phase: "day", message: "finished without a result".to_string() }
+            };
+            failed(context, name, error)
+        }
+    }
+}
+
+pub fn run_sequential(context: &mut Context, days: &[Day]) {
+    let start = Instant::now();
+
+    for (name, solver) in days {
+        run_day(context, name, *solver);
+        context.report_pending();
+    }
+
+    context.threads = 1;
+    context.wall_clock = start.elapsed();
+}
+
+pub fn run_parallel(context: &mut Context, days: &[Day], threads: usize) {
+    let start = Instant::now();
+    let threads = threads.clamp(1, days.len().max(1));
+    let next_day = AtomicUsize::new(0);
+    let mut results = vec![Vec::new(); days.len()];
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads).map(|_| {
+            let mut worker = context.for_worker();
+            let next_day = &next_day;
+
+            scope.spawn(move || {
+                let mut done = Vec::new();
+                loop {
+                    let index = next_day.fetch_add(1, Ordering::Relaxed);
+                    let Some((name, solver)) = days.get(index) else {
+                        return done;
+                    };
+                    run_day(&mut worker, name, *solver);
+                    done.push((index, std::mem::take(&mut worker.pending)));
+                }
+            })
+        }).collect();
+
+        for worker in workers {
+            for (index, records) in worker.join().expect("worker thread panicked") {
+                results[index] = records;
+            }
+        }
+    });
+
+    // merged in day order, so the output is the same as in a sequential run
+    for records in results {
+        context.pending.extend(records);
+        context.report_pending();
+    }
+
+    context.threads = threads;
+    context.wall_clock = start.elapsed();
+}
@@ -1,5 +1,6 @@
-use std::fmt::{Debug, Formatter};
-use std::ops::{Add, Index, IndexMut, Mul};
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::{Add, Deref, DerefMut, Index, IndexMut, Mul};
 use geo::{Coord, CoordNum};
 use crate::util::Direction::{EAST, NORTH, SOUTH, WEST};
 
@@ -66,6 +67,9 @@ impl Mul<Direction> for i32 {
 impl Direction {
     pub const ALL: [Direction; 4] = [EAST, SOUTH, WEST, NORTH];
 
+    // the offsets of all eight surrounding cells, clockwise from east
+    pub const OFFSETS_8: [(i32, i32); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
     pub fn opposite(self) -> Self {
         match self {
             EAST => WEST,
@@ -80,16 +84,12 @@ impl Direction {
 pub struct Index2D(pub i32, pub i32);
 
 impl Index2D {
-    pub fn shift_into_range(mut self, start: Index2D, end: Index2D) -> Self {
-        if self.0 < 0 || self.0 > end.0 {
-            self.0 = (self.0 + end.0) % end.0
-        }
-
-        if self.1 < 0 || self.1 > end.1 {
-            self.1 = (self.1 + end.1) % end.1
-        }
-
-        self
+    // wraps both coordinates into `start..end`, `end` being exclusive
+    pub fn shift_into_range(self, start: Index2D, end: Index2D) -> Self {
+        Self(
+            start.0 + (self.0 - start.0).rem_euclid(end.0 - start.0),
+            start.1 + (self.1 - start.1).rem_euclid(end.1 - start.1),
+        )
     }
 }
 
@@ -129,6 +129,67 @@ impl Add<Step> for Index2D {
     }
 }
 
+// positions drawn as `marker` instead of their cell when rendering a grid, e.g. a path or the visited cells
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    positions: HashSet<Index2D>,
+    marker: char,
+}
+
+impl Overlay {
+    pub fn new(positions: impl IntoIterator<Item=Index2D>, marker: char) -> Self {
+        Self { positions: positions.into_iter().collect(), marker }
+    }
+}
+
+// one line per row, without a trailing newline
+fn render_grid<T, G: TwoDimensional + Index<Index2D, Output=T>>(
+    grid: &G,
+    out: &mut impl fmt::Write,
+    cell: impl Fn(&T) -> char,
+    overlay: Option<&Overlay>,
+) -> fmt::Result {
+    for y in 0..grid.rows() as i32 {
+        if y > 0 {
+            out.write_char('\n')?;
+        }
+        for x in 0..grid.columns() as i32 {
+            let index = Index2D(x, y);
+            match overlay {
+                Some(overlay) if overlay.positions.contains(&index) => out.write_char(overlay.marker)?,
+                _ => out.write_char(cell(&grid[index]))?
+            }
+        }
+    }
+    Ok(())
+}
+
+// sized for one byte per cell and a newline per row, which is exact for ASCII cells
+fn render_grid_to_string<T, G: TwoDimensional + Index<Index2D, Output=T>>(
+    grid: &G,
+    cell: impl Fn(&T) -> char,
+    overlay: Option<&Overlay>,
+) -> String {
+    let mut rendered = String::with_capacity(grid.rows() * (grid.columns() + 1));
+    render_grid(grid, &mut rendered, cell, overlay).unwrap();
+    rendered
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GridError {
+    Empty { row: usize },
+    Ragged { row: usize, expected: usize, found: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty { row } => write!(f, "empty grid at row {}", row),
+            GridError::Ragged { row, expected, found } => write!(f, "row {} has {} columns, expected {}", row, found, expected),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Flat2DArray<T> {
     contents: Vec<T>,
@@ -152,13 +213,189 @@ impl<T> Flat2DArray<T> {
         self.contents
     }
 
-    pub fn transpose(&self) -> Transposed<T> {
+    pub fn transpose(&self) -> Transposed<&Self> {
         Transposed(self)
     }
 
+    pub fn transpose_mut(&mut self) -> Transposed<&mut Self> {
+        Transposed(self)
+    }
+
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        assert!(!self.contents.is_empty(), "An empty grid can not be tiled");
+        Tiled(self)
+    }
+
+    // like indexing, positions outside of the grid yield the out of bounds element
+    pub fn neighbours4(&self, index: Index2D) -> impl Iterator<Item=(Index2D, &T)> + '_ {
+        Direction::ALL.into_iter().map(move |direction| {
+            let neighbour = index + direction;
+            (neighbour, &self[neighbour])
+        })
+    }
+
+    pub fn neighbours8(&self, index: Index2D) -> impl Iterator<Item=(Index2D, &T)> + '_ {
+        Direction::OFFSETS_8.into_iter().map(move |(dx, dy)| {
+            let neighbour = Index2D(index.0 + dx, index.1 + dy);
+            (neighbour, &self[neighbour])
+        })
+    }
+
+    // empty for rows and columns outside of the grid
+    pub fn row(&self, y: i32) -> std::slice::Iter<'_, T> {
+        if y < 0 || y >= self.rows() as i32 {
+            return [].iter();
+        }
+        let start = y as usize * self.columns;
+        self.contents[start..start + self.columns].iter()
+    }
+
+    pub fn column(&self, x: i32) -> std::iter::StepBy<std::slice::Iter<'_, T>> {
+        let start = if x < 0 || x >= self.columns as i32 { self.contents.len() } else { x as usize };
+        self.contents[start..].iter().step_by(self.columns)
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item=(Index2D, &T)> + '_ {
+        self.contents.iter().enumerate()
+            .map(|(linear, element)| (Index2D((linear % self.columns) as i32, (linear / self.columns) as i32), element))
+    }
+
+    pub fn positions_where<'a>(&'a self, predicate: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item=Index2D> + 'a {
+        self.indexed_iter().filter(move |(_, element)| predicate(element)).map(|(index, _)| index)
+    }
+
+    pub fn render(&self, cell: impl Fn(&T) -> char, overlay: Option<&Overlay>) -> String {
+        render_grid_to_string(self, cell, overlay)
+    }
+
+    // for Display and Debug implementations of types holding a grid
+    pub fn render_to(&self, f: &mut Formatter<'_>, cell: impl Fn(&T) -> char, overlay: Option<&Overlay>) -> fmt::Result {
+        render_grid(self, f, cell, overlay)
+    }
+
     fn linearize_index(&self, x: i32, y: i32) -> usize {
         y as usize * self.columns + x as usize
     }
+
+    // one cell per byte, one row per line
+    pub fn parse_grid(input: &str, out_of_bounds_element: T, cell: impl FnMut(u8, Index2D) -> T) -> Result<Self, GridError> {
+        Self::parse_grid_with_markers(input, out_of_bounds_element, &[], cell).map(|(grid, _)| grid)
+    }
+
+    // also returns the positions of all bytes in `markers`, e.g. the start 'S', in reading order
+    pub fn parse_grid_with_markers(
+        input: &str,
+        out_of_bounds_element: T,
+        markers: &[u8],
+        cell: impl FnMut(u8, Index2D) -> T,
+    ) -> Result<(Self, Vec<(u8, Index2D)>), GridError> {
+        Self::parse_lines(input.lines().enumerate(), input.len(), out_of_bounds_element, markers, cell)
+    }
+
+    // rows in errors are counted from the start of the whole input, starting at 1. `capacity` is the number of cells
+    // to reserve up front, the length of the input is close enough
+    fn parse_lines<'a>(
+        lines: impl IntoIterator<Item=(usize, &'a str)>,
+        capacity: usize,
+        out_of_bounds_element: T,
+        markers: &[u8],
+        mut cell: impl FnMut(u8, Index2D) -> T,
+    ) -> Result<(Self, Vec<(u8, Index2D)>), GridError> {
+        let mut contents = Vec::with_capacity(capacity);
+        let mut columns = None;
+        let mut found = Vec::new();
+
+        for (y, (row, line)) in lines.into_iter().enumerate() {
+            let expected = *columns.get_or_insert(line.len());
+            if expected == 0 {
+                return Err(GridError::Empty { row: row + 1 });
+            }
+            if line.len() != expected {
+                return Err(GridError::Ragged { row: row + 1, expected, found: line.len() });
+            }
+
+            for (x, byte) in line.bytes().enumerate() {
+                let index = Index2D(x as i32, y as i32);
+                if markers.contains(&byte) {
+                    found.push((byte, index))
+                }
+                contents.push(cell(byte, index))
+            }
+        }
+
+        let columns = columns.ok_or(GridError::Empty { row: 1 })?;
+        Ok((Self::from_data(out_of_bounds_element, contents, columns), found))
+    }
+}
+
+// the marker bytes found while parsing a grid and where they were, in reading order
+pub type Markers = Vec<(u8, Index2D)>;
+
+impl Flat2DArray<()> {
+    // only the positions of the bytes in `markers` and the number of rows and columns, for inputs where the other
+    // cells do not matter
+    pub fn markers(input: &str, markers: &[u8]) -> Result<(Markers, usize, usize), GridError> {
+        let (grid, found) = Self::parse_grid_with_markers(input, (), markers, |_, _| ())?;
+        Ok((found, grid.rows(), grid.columns))
+    }
+}
+
+impl<T: Clone> Flat2DArray<T> {
+    // several grids separated by blank lines, the indices passed to `cell` start over in every grid
+    pub fn parse_grids(input: &str, out_of_bounds_element: T, mut cell: impl FnMut(u8, Index2D) -> T) -> Result<Vec<Self>, GridError> {
+        let mut grids = Vec::new();
+        let mut lines = input.lines().enumerate().peekable();
+
+        while lines.peek().is_some() {
+            let block: Vec<_> = lines.by_ref().take_while(|(_, line)| !line.is_empty()).collect();
+            if !block.is_empty() {
+                let capacity = block.iter().map(|(_, line)| line.len()).sum();
+                grids.push(Self::parse_lines(block, capacity, out_of_bounds_element.clone(), &[], &mut cell)?.0);
+            }
+        }
+
+        Ok(grids)
+    }
+}
+
+impl<T: Clone> Flat2DArray<T> {
+    // a new grid of the given size, where the cell at (x, y) is taken from `source(x, y)` in this one
+    fn remap(&self, rows: usize, columns: usize, source: impl Fn(i32, i32) -> Index2D) -> Self {
+        let mut contents = Vec::with_capacity(rows * columns);
+        for y in 0..rows as i32 {
+            for x in 0..columns as i32 {
+                contents.push(self[source(x, y)].clone())
+            }
+        }
+
+        Self { contents, columns, out_of_bounds_element: self.out_of_bounds_element.clone() }
+    }
+
+    pub fn transposed(&self) -> Self {
+        self.remap(self.columns, self.rows(), |x, y| Index2D(y, x))
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        let rows = self.rows() as i32;
+        self.remap(self.columns, self.rows(), |x, y| Index2D(y, rows - 1 - x))
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        let columns = self.columns as i32;
+        self.remap(self.columns, self.rows(), |x, y| Index2D(columns - 1 - y, x))
+    }
+
+    // mirrored left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let columns = self.columns as i32;
+        self.remap(self.rows(), self.columns, |x, y| Index2D(columns - 1 - x, y))
+    }
+
+    // mirrored top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let rows = self.rows() as i32;
+        self.remap(self.rows(), self.columns, |x, y| Index2D(x, rows - 1 - y))
+    }
 }
 
 impl <T: Default + Clone> Flat2DArray<T> {
@@ -201,9 +438,10 @@ impl<T> IndexMut<Index2D> for Flat2DArray<T> {
     }
 }
 
-pub struct Transposed<'a, T>(&'a Flat2DArray<T>);
+// a view with rows and columns swapped, mutable when made with `transpose_mut`
+pub struct Transposed<G>(G);
 
-impl<T> Index<Index2D> for Transposed<'_, T> {
+impl<T, G: Deref<Target=Flat2DArray<T>>> Index<Index2D> for Transposed<G> {
     type Output = T;
 
     fn index(&self, index: Index2D) -> &Self::Output {
@@ -213,7 +451,25 @@ impl<T> Index<Index2D> for Transposed<'_, T> {
     }
 }
 
-impl<T> TwoDimensional for Transposed<'_, T> {
+impl<T, G: DerefMut<Target=Flat2DArray<T>>> IndexMut<Index2D> for Transposed<G> {
+    fn index_mut(&mut self, index: Index2D) -> &mut Self::Output {
+        let Index2D(x, y) = index;
+
+        &mut self.0[Index2D(y, x)]
+    }
+}
+
+impl<T, G: Deref<Target=Flat2DArray<T>>> Transposed<G> {
+    pub fn render(&self, cell: impl Fn(&T) -> char, overlay: Option<&Overlay>) -> String {
+        render_grid_to_string(self, cell, overlay)
+    }
+
+    pub fn render_to(&self, f: &mut Formatter<'_>, cell: impl Fn(&T) -> char, overlay: Option<&Overlay>) -> fmt::Result {
+        render_grid(self, f, cell, overlay)
+    }
+}
+
+impl<T, G: Deref<Target=Flat2DArray<T>>> TwoDimensional for Transposed<G> {
     fn rows(&self) -> usize {
         self.0.columns
     }
@@ -222,3 +478,105 @@ impl<T> TwoDimensional for Transposed<'_, T> {
         self.0.rows()
     }
 }
+
+// the grid repeated infinitely in every direction, any index is in range
+pub struct Tiled<'a, T>(&'a Flat2DArray<T>);
+
+impl<T> Tiled<'_, T> {
+    // the position within the grid that `index` is a copy of
+    pub fn wrap(&self, index: Index2D) -> Index2D {
+        index.shift_into_range(Index2D(0, 0), Index2D(self.0.columns as i32, self.0.rows() as i32))
+    }
+
+    // which copy of the grid `index` falls into, the original being Index2D(0, 0)
+    pub fn tile(&self, index: Index2D) -> Index2D {
+        Index2D(index.0.div_euclid(self.0.columns as i32), index.1.div_euclid(self.0.rows() as i32))
+    }
+}
+
+impl<T> Index<Index2D> for Tiled<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Index2D) -> &Self::Output {
+        &self.0[self.wrap(index)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3 columns, 2 rows
+    fn grid() -> Flat2DArray<char> {
+        Flat2DArray::parse_grid("abc\ndef", ' ', |byte, _| byte as char).unwrap()
+    }
+
+    fn text(grid: &Flat2DArray<char>) -> String {
+        grid.render(|cell| *cell, None)
+    }
+
+    #[test]
+    fn rotations_and_flips() {
+        let grid = grid();
+        assert_eq!(text(&grid.transposed()), "ad\nbe\ncf");
+        assert_eq!(text(&grid.rotate_cw()), "da\neb\nfc");
+        assert_eq!(text(&grid.rotate_ccw()), "cf\nbe\nad");
+        assert_eq!(text(&grid.flip_horizontal()), "cba\nfed");
+        assert_eq!(text(&grid.flip_vertical()), "def\nabc");
+    }
+
+    #[test]
+    fn rotations_round_trip() {
+        let grid = grid();
+        assert_eq!(text(&grid.rotate_cw().rotate_ccw()), text(&grid));
+        assert_eq!(text(&grid.rotate_ccw().rotate_cw()), text(&grid));
+        assert_eq!(text(&grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw()), text(&grid));
+        assert_eq!(text(&grid.flip_horizontal().flip_horizontal()), text(&grid));
+        assert_eq!(text(&grid.flip_vertical().flip_vertical()), text(&grid));
+    }
+
+    #[test]
+    fn transposed_view_writes_through() {
+        let mut grid = grid();
+        assert_eq!(grid.transpose().render(|cell| *cell, None), "ad\nbe\ncf");
+        grid.transpose_mut()[Index2D(1, 2)] = 'X';
+        assert_eq!(text(&grid), "abc\ndeX");
+    }
+
+    #[test]
+    fn tiled_wraps_negative_indices() {
+        let grid = grid();
+        let tiled = grid.tiled();
+        assert_eq!(tiled.wrap(Index2D(-1, 0)), Index2D(2, 0));
+        assert_eq!(tiled.tile(Index2D(-1, 0)), Index2D(-1, 0));
+        assert_eq!(tiled.wrap(Index2D(-4, -3)), Index2D(2, 1));
+        assert_eq!(tiled.tile(Index2D(-4, -3)), Index2D(-2, -2));
+        assert_eq!(tiled.wrap(Index2D(-3, -2)), Index2D(0, 0));
+        assert_eq!(tiled.tile(Index2D(-3, -2)), Index2D(-1, -1));
+        assert_eq!(tiled[Index2D(-1, -1)], 'f');
+        assert_eq!(tiled[Index2D(5, 3)], 'f');
+    }
+
+    #[test]
+    fn shift_into_range_respects_start() {
+        let (start, end) = (Index2D(10, -2), Index2D(13, 2));
+        assert_eq!(Index2D(12, -3).shift_into_range(start, end), Index2D(12, 1));
+        assert_eq!(Index2D(9, 2).shift_into_range(start, end), Index2D(12, -2));
+        assert_eq!(Index2D(11, 0).shift_into_range(start, end), Index2D(11, 0));
+    }
+
+    #[test]
+    fn parse_grid_errors() {
+        let parse = |input| Flat2DArray::parse_grid(input, (), |_, _| ()).map(|_| ());
+        assert_eq!(parse("abc\nde"), Err(GridError::Ragged { row: 2, expected: 3, found: 2 }));
+        assert_eq!(parse("abc\n\ndef"), Err(GridError::Ragged { row: 2, expected: 3, found: 0 }));
+        assert_eq!(parse("\nabc"), Err(GridError::Empty { row: 1 }));
+        assert_eq!(parse(""), Err(GridError::Empty { row: 1 }));
+    }
+
+    #[test]
+    fn parse_grids_counts_rows_from_the_start_of_the_input() {
+        let parsed = Flat2DArray::parse_grids("ab\ncd\n\nab\nc", false, |byte, _| byte == b'a').map(|grids| grids.len());
+        assert_eq!(parsed, Err(GridError::Ragged { row: 5, expected: 2, found: 1 }));
+    }
+}
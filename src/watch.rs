@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::input::{InputSource, Inputs};
+use crate::output::{DayRecord, Format};
+use crate::runner;
+use crate::{Context, Solver};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+struct Watched {
+    inputs: Inputs,
+    path: PathBuf,
+    // None until the first run, so every file is run once at startup
+    modified: Option<Option<SystemTime>>,
+    previous: Option<DayRecord>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn signed_difference(previous: Duration, current: Duration) -> String {
+    let percent = if previous.is_zero() {
+        0.0
+    } else {
+        100.0 * (current.as_secs_f64() / previous.as_secs_f64() - 1.0)
+    };
+    if current >= previous {
+        format!("+{:?} ({:+.1}%)", current - previous, percent)
+    } else {
+        format!("-{:?} ({:+.1}%)", previous - current, percent)
+    }
+}
+
+fn answer_change(part: &str, previous: &Option<String>, current: &Option<String>) -> Option<String> {
+    match (previous, current) {
+        (Some(previous), Some(current)) if previous != current => Some(format!("{} {} -> {}", part, previous, current)),
+        _ => None
+    }
+}
+
+fn changes(previous: &DayRecord, current: &DayRecord) -> String {
+    let mut changes: Vec<_> = [
+        answer_change("part1", &previous.part1, &current.part1),
+        answer_change("part2", &previous.part2, &current.part2),
+    ].into_iter().flatten().collect();
+    if changes.is_empty() {
+        changes.push("same answers".to_string())
+    }
+    changes.push(format!("parse {}", signed_difference(previous.parse, current.parse)));
+    if current.part1.is_some() {
+        changes.push(format!("part 1 {}", signed_difference(previous.part1_duration, current.part1_duration)));
+    }
+    if current.part2.is_some() {
+        changes.push(format!("part 2 {}", signed_difference(previous.part2_duration, current.part2_duration)));
+    }
+
+    format!("    since the last run: {}", changes.join(", "))
+}
+
+// re-runs the day every time its input or the example file changes, until interrupted
+pub fn run(context: &mut Context, day: usize, solver: &'static dyn Solver, example: Option<PathBuf>) -> i32 {
+    let name = format!("day{}", day);
+    let inputs = context.settings.inputs.clone();
+    let InputSource::File(path) = inputs.source_for(&name) else {
+        eprintln!("Cannot watch {}, as it reads its input from stdin", name);
+        return 2;
+    };
+
+    // every watched file is an override, so variants of the input are not run along with it
+    let watching = |path: PathBuf| {
+        let mut inputs = inputs.clone();
+        inputs.set_override(day, InputSource::File(path.clone()));
+        Watched { inputs, path, modified: None, previous: None }
+    };
+    let mut watched = vec![watching(path)];
+    watched.extend(example.map(watching));
+
+    let format = context.settings.format;
+    if format == Format::Text {
+        println!("Watching {}, press Ctrl-C to stop", watched.iter().map(|it| it.path.display().to_string()).collect::<Vec<_>>().join(" and "));
+    }
+
+    loop {
+        for watched in &mut watched {
+            let modified = modified(&watched.path);
+            if watched.modified == Some(modified) {
+                continue;
+            }
+            watched.modified = Some(modified);
+
+            if format == Format::Text {
+                println!("-- {} --", watched.path.display());
+            }
+            context.settings.inputs = watched.inputs.clone();
+            runner::run_day(context, &name, solver);
+
+            for record in std::mem::take(&mut context.pending) {
+                let previous = watched.previous.replace(record.clone());
+                context.report(record.clone());
+                if let (Some(previous), Format::Text, None) = (previous, format, &record.error) {
+                    if previous.error.is_none() {
+                        println!("{}", changes(&previous, &record))
+                    }
+                }
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL)
+    }
+}
@@ -1,3 +1,6 @@
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
+
 const STRING_VALUE_PAIRS: [(&'static str, i64); 9] = [
     ("one", -1),
     ("two", -2),
@@ -73,4 +76,22 @@ fn parse(mut input: &str) -> Vec<i64> {
     result
 }
 
-simple_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day1;
+
+impl Puzzle for Day1 {
+    type Input = Vec<i64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        Ok(parse(&input))
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
@@ -1,7 +1,9 @@
 use geo::{Contains, Coord, LineString, Polygon};
 
-use crate::util::{Flat2DArray, Index2D, TwoDimensional};
+use crate::util::{Flat2DArray, GridError, Index2D, TwoDimensional};
 use crate::util::Direction::{self, EAST, NORTH, SOUTH, WEST};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum PipeSegment {
@@ -61,46 +63,25 @@ impl PipeSegment {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     data: Flat2DArray<PipeSegment>,
     start: Index2D,
 }
 
-fn parse(input: &str) -> Input {
-    let mut columns = usize::MAX;
-    let mut start = Index2D(-1, -1);
-    let mut data = Vec::new();
-
-    for (y, line) in input.lines().enumerate() {
-        if columns == usize::MAX {
-            columns = line.len();
-        }
-
-        for (x, byte) in line.bytes().enumerate() {
-            let next = match byte {
-                b'|' => PipeSegment::Vertical,
-                b'-' => PipeSegment::Horizontal,
-                b'7' => PipeSegment::WestToSouth,
-                b'F' => PipeSegment::SouthToEast,
-                b'L' => PipeSegment::EastToNorth,
-                b'J' => PipeSegment::NorthToWest,
-                b'S' => {
-                    start = Index2D(x as i32, y as i32);
-                    PipeSegment::Starter
-                }
-                _ => {
-                    PipeSegment::Ground
-                }
-            };
-
-            data.push(next)
-        }
-    }
-
-    Input {
-        data: Flat2DArray::from_data(PipeSegment::Ground, data, columns),
-        start,
-    }
+fn parse(input: &str) -> Result<Input, GridError> {
+    let (data, markers) = Flat2DArray::parse_grid_with_markers(input, PipeSegment::Ground, b"S", |byte, _| match byte {
+        b'|' => PipeSegment::Vertical,
+        b'-' => PipeSegment::Horizontal,
+        b'7' => PipeSegment::WestToSouth,
+        b'F' => PipeSegment::SouthToEast,
+        b'L' => PipeSegment::EastToNorth,
+        b'J' => PipeSegment::NorthToWest,
+        b'S' => PipeSegment::Starter,
+        _ => PipeSegment::Ground
+    })?;
+    let start = markers.first().map_or(Index2D(-1, -1), |&(_, start)| start);
+
+    Ok(Input { data, start })
 }
 
 fn first_step(start: Index2D, layout: &Flat2DArray<PipeSegment>) -> (Direction, Index2D) {
@@ -180,5 +161,22 @@ fn solve_2(input: &Input) -> i32 {
     count
 }
 
+pub struct Day10;
 
-simple_solution!(parse, solve_1, solve_2);
\ No newline at end of file
+impl Puzzle for Day10 {
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = i32;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        parse(&input).map_err(SolveError::from)
+    }
+
+    fn part1(&self, input: &Self::Input) -> usize {
+        solve_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> i32 {
+        solve_2(input)
+    }
+}
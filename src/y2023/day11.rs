@@ -1,32 +1,24 @@
 use std::ops::Range;
 
-use crate::util::Index2D;
+use crate::util::{Flat2DArray, GridError, Index2D};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     galaxies: Vec<Index2D>,
     rows: usize,
     columns: usize,
 }
 
-fn parse(input: &str) -> Input {
-    let mut result = Input {
-        galaxies: vec![],
-        rows: 0,
-        columns: 0,
-    };
-    for (y, line) in input.lines().enumerate() {
-        for (x, byte) in line.bytes().enumerate() {
-            if byte == b'#' {
-                result.galaxies.push(Index2D(x as i32, y as i32))
-            }
-        }
-
-        result.rows = y + 1;
-        result.columns = line.len();
-    }
+fn parse(input: &str) -> Result<Input, GridError> {
+    let (galaxies, rows, columns) = Flat2DArray::markers(input, b"#")?;
 
-    result
+    Ok(Input {
+        galaxies: galaxies.into_iter().map(|(_, galaxy)| galaxy).collect(),
+        rows,
+        columns,
+    })
 }
 
 fn part1(input: &Input) -> u64 {
@@ -100,4 +92,22 @@ fn range_dist(weights: &Vec<u64>, range: Range<usize>) -> u64 {
     range.map(|x| weights[x]).sum()
 }
 
-simple_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day11;
+
+impl Puzzle for Day11 {
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        parse(&input).map_err(SolveError::from)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
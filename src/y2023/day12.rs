@@ -8,6 +8,8 @@ use nom::combinator::{map, value};
 use nom::IResult;
 use nom::multi::{many1, separated_list1};
 use nom::sequence::tuple;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 enum SpringState {
@@ -17,7 +19,7 @@ enum SpringState {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Problem {
+pub struct Problem {
     states: Vec<SpringState>,
     broken_series: Vec<u32>,
 }
@@ -186,5 +188,22 @@ fn part_2(input: &Vec<Problem>) -> usize {
     count
 }
 
+pub struct Day12;
 
-nom_solution!(parse, part_1, part_2);
\ No newline at end of file
+impl Puzzle for Day12 {
+    type Input = Vec<Problem>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> usize {
+        part_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> usize {
+        part_2(input)
+    }
+}
@@ -1,7 +1,9 @@
 use std::mem::swap;
 use std::ops::Index;
 
-use crate::util::{Flat2DArray, Index2D, TwoDimensional};
+use crate::util::{Flat2DArray, GridError, Index2D, TwoDimensional};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 trait ReflectionLineCandidate {
     fn index(&self) -> i32;
@@ -35,34 +37,8 @@ impl ReflectionLineCandidate for (i32, bool) {
     }
 }
 
-fn parse_maps(input: &str) -> Vec<Flat2DArray<bool>> {
-    let mut target = Vec::new();
-    let mut this_width = usize::MAX;
-    let mut buffer = Vec::new();
-
-    for line in input.lines() {
-        if line == "" {
-            let map = Flat2DArray::from_data(false, buffer.clone(), this_width);
-            target.push(map);
-            buffer.clear();
-            this_width = usize::MAX;
-            continue;
-        }
-
-        let bytes = line.as_bytes();
-        if this_width == usize::MAX {
-            this_width = bytes.len();
-        }
-
-        buffer.extend(bytes.iter().map(|x| *x == b'#'))
-    }
-
-    if !buffer.is_empty() {
-        let map = Flat2DArray::from_data(false, buffer.clone(), this_width);
-        target.push(map);
-    }
-
-    target
+fn parse_maps(input: &str) -> Result<Vec<Flat2DArray<bool>>, GridError> {
+    Flat2DArray::parse_grids(input, false, |byte, _| byte == b'#')
 }
 
 fn find_reflection<Out: Eq + Copy, Container: Index<Index2D, Output=Out> + TwoDimensional>(data: &Container) -> Vec<i32> {
@@ -150,4 +126,23 @@ fn solve_part2(input: &Vec<Flat2DArray<bool>>) -> i32 {
 
     sum
 }
-simple_solution!(parse_maps, solve_part1, solve_part2);
\ No newline at end of file
+
+pub struct Day13;
+
+impl Puzzle for Day13 {
+    type Input = Vec<Flat2DArray<bool>>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        parse_maps(&input).map_err(SolveError::from)
+    }
+
+    fn part1(&self, input: &Self::Input) -> i32 {
+        solve_part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> i32 {
+        solve_part2(input)
+    }
+}
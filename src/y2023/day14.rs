@@ -3,6 +3,8 @@ use std::fmt::{Display, Formatter, Write};
 use std::rc::Rc;
 
 use crate::util::Index2D;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum RockType {
@@ -24,7 +26,7 @@ impl Display for Rock {
 
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
-struct Input {
+pub struct Input {
     max_row: i32,
     rocks: Vec<Rock>,
 }
@@ -220,4 +222,22 @@ fn part_2(input: &Input) -> i32 {
     input.load()
 }
 
-simple_solution!(parse, part_1, part_2);
\ No newline at end of file
+pub struct Day14;
+
+impl Puzzle for Day14 {
+    type Input = Input;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        Ok(parse(&input))
+    }
+
+    fn part1(&self, input: &Self::Input) -> i32 {
+        part_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> i32 {
+        part_2(input)
+    }
+}
@@ -4,6 +4,8 @@ use nom::character::complete::{alpha1, u32 as parse_u32};
 use nom::combinator::map;
 use nom::IResult;
 use nom::sequence::{terminated, tuple};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug, Clone)]
 struct SlotEntry<'a>(&'a str, u32);
@@ -98,4 +100,22 @@ fn part2(input: &String) -> usize {
     map.checksum()
 }
 
-unparsed_solution!(part1, part2);
\ No newline at end of file
+pub struct Day15;
+
+impl Puzzle for Day15 {
+    type Input = String;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        Ok(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> usize {
+        part2(input)
+    }
+}
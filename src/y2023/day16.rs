@@ -1,8 +1,10 @@
-use crate::util::{Direction, Flat2DArray, Index2D, TwoDimensional};
+use crate::util::{Direction, Flat2DArray, GridError, Index2D, TwoDimensional};
 use Tile::*;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Outside,
     Empty,
     TiltCCW,
@@ -11,23 +13,14 @@ enum Tile {
     SplitHorizontal,
 }
 
-fn parse(input: &str) -> Flat2DArray<Tile> {
-    let mut buffer = Vec::with_capacity(input.len());
-    let mut line_length = usize::MAX;
-    for line in input.lines() {
-        for byte in line.bytes() {
-            buffer.push(match byte {
-                b'\\' => TiltCCW,
-                b'/' => TiltCW,
-                b'|' => SplitVertical,
-                b'-' => SplitHorizontal,
-                _ => Empty,
-            });
-            line_length = line.len()
-        }
-    }
-
-    Flat2DArray::from_data(Outside, buffer, line_length)
+fn parse(input: &str) -> Result<Flat2DArray<Tile>, GridError> {
+    Flat2DArray::parse_grid(input, Outside, |byte, _| match byte {
+        b'\\' => TiltCCW,
+        b'/' => TiltCW,
+        b'|' => SplitVertical,
+        b'-' => SplitHorizontal,
+        _ => Empty,
+    })
 }
 
 fn trace(
@@ -115,4 +108,22 @@ fn part2(input: &Flat2DArray<Tile>) -> usize {
     }).max().unwrap()
 }
 
-simple_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day16;
+
+impl Puzzle for Day16 {
+    type Input = Flat2DArray<Tile>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        parse(&input).map_err(SolveError::from)
+    }
+
+    fn part1(&self, input: &Self::Input) -> usize {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> usize {
+        part2(input)
+    }
+}
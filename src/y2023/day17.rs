@@ -1,7 +1,9 @@
-use crate::util::{Flat2DArray, Index2D, TwoDimensional};
+use crate::util::{Flat2DArray, GridError, Index2D, TwoDimensional};
 
 use pathfinding::directed::astar::astar;
-use crate::day17::ForcedDirection::{EITHER, HORIZONTAL, VERTICAL};
+use crate::y2023::day17::ForcedDirection::{EITHER, HORIZONTAL, VERTICAL};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum ForcedDirection {
@@ -19,17 +21,8 @@ struct Position {
     direction: ForcedDirection,
 }
 
-fn parse(input: &str) -> Flat2DArray<i32> {
-    let mut buffer = Vec::with_capacity(input.len());
-    let mut line_length = usize::MAX;
-    for line in input.lines() {
-        for byte in line.bytes() {
-            buffer.push((byte - b'0') as i32)
-        }
-        line_length = line.len();
-    }
-
-    Flat2DArray::from_data(HUGE, buffer, line_length)
+fn parse(input: &str) -> Result<Flat2DArray<i32>, GridError> {
+    Flat2DArray::parse_grid(input, HUGE, |byte, _| (byte - b'0') as i32)
 }
 
 
@@ -248,4 +241,22 @@ fn part_2(input: &Flat2DArray<i32>) -> i32 {
     ).unwrap().1
 }
 
-simple_solution!(parse, part_1,  part_2);
\ No newline at end of file
+pub struct Day17;
+
+impl Puzzle for Day17 {
+    type Input = Flat2DArray<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        parse(&input).map_err(SolveError::from)
+    }
+
+    fn part1(&self, input: &Self::Input) -> i32 {
+        part_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> i32 {
+        part_2(input)
+    }
+}
@@ -6,9 +6,11 @@ use nom::combinator::{map, map_res, value};
 use nom::IResult;
 use nom::multi::separated_list1;
 use nom::sequence::tuple;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug)]
-struct DigInstruction {
+pub struct DigInstruction {
     delta: Coord,
 }
 
@@ -85,4 +87,22 @@ fn part2(input: &Vec<(DigInstruction, DigInstruction)>) -> f64 {
     area(input.iter().map(|(_, i)| i))
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day18;
+
+impl Puzzle for Day18 {
+    type Input = Vec<(DigInstruction, DigInstruction)>;
+    type Answer1 = f64;
+    type Answer2 = f64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> f64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> f64 {
+        part2(input)
+    }
+}
@@ -7,6 +7,8 @@ use nom::combinator::{map, value};
 use nom::IResult;
 use nom::multi::{separated_list0, separated_list1};
 use nom::sequence::tuple;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, Clone)]
 struct Part {
@@ -162,7 +164,7 @@ impl InputRange {
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub struct Input {
     ruleset: HashMap<String, Rule>,
     parts: Vec<Part>,
 }
@@ -327,4 +329,22 @@ fn part2(input: &Input) -> usize {
     sum
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day19;
+
+impl Puzzle for Day19 {
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u32 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> usize {
+        part2(input)
+    }
+}
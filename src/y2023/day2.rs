@@ -5,6 +5,8 @@ use nom::combinator::map;
 use nom::IResult;
 use nom::multi::separated_list1;
 use nom::sequence::tuple;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, Copy, Clone)]
 enum Color {
@@ -14,7 +16,7 @@ enum Color {
 }
 
 #[derive(Debug, Copy, Clone, Default)]
-struct BagState {
+pub struct BagState {
     red: u64,
     blue: u64,
     green: u64,
@@ -95,4 +97,22 @@ fn part2(input: &Vec<BagState>) -> u64 {
     })
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day2;
+
+impl Puzzle for Day2 {
+    type Input = Vec<BagState>;
+    type Answer1 = i32;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> i32 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
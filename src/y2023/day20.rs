@@ -9,8 +9,10 @@ use nom::IResult;
 use nom::multi::{separated_list0, separated_list1};
 use nom::sequence::tuple;
 
-use crate::day20::Module::{Broadcaster, Conjunction, FlipFlop};
+use crate::y2023::day20::Module::{Broadcaster, Conjunction, FlipFlop};
 use crate::util::FixedLengthAsciiString;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, Clone)]
 enum Module {
@@ -88,7 +90,7 @@ struct Wire {
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub struct Input {
     broadcaster_index: usize,
     modules: Vec<WiredModule>,
 }
@@ -254,4 +256,22 @@ fn part_2(input: &Input) -> usize {
     feeders.values().map(|x| x.unwrap()).product()
 }
 
-nom_solution!(parse_and_reformat, part_1, part_2);
\ No newline at end of file
+pub struct Day20;
+
+impl Puzzle for Day20 {
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse_and_reformat, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> usize {
+        part_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> usize {
+        part_2(input)
+    }
+}
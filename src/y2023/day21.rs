@@ -1,7 +1,9 @@
 use std::cell::{Ref, RefCell};
 use std::collections::{HashSet, VecDeque};
 
-use crate::util::{Direction, Flat2DArray, Index2D, TwoDimensional};
+use crate::util::{Direction, Flat2DArray, GridError, Index2D, TwoDimensional};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum Reachability {
@@ -11,7 +13,7 @@ enum Reachability {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     rocks: HashSet<Index2D>,
     step_counts: RefCell<Option<Flat2DArray<Reachability>>>,
 }
@@ -31,18 +33,11 @@ impl Input {
     }
 }
 
-fn parse(input: &str) -> Input {
-    let mut rocks = HashSet::new();
+fn parse(input: &str) -> Result<Input, GridError> {
+    let (rocks, _, _) = Flat2DArray::markers(input, b"#")?;
+    let rocks = rocks.into_iter().map(|(_, Index2D(x, y))| Index2D(y, x)).collect();
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, byte) in line.bytes().enumerate() {
-            if byte == b'S' {} else if byte == b'#' {
-                rocks.insert(Index2D(y as i32, x as i32));
-            }
-        }
-    }
-
-    Input { rocks, step_counts: RefCell::new(None) }
+    Ok(Input { rocks, step_counts: RefCell::new(None) })
 }
 
 fn part1(input: &Input) -> usize {
@@ -115,5 +110,22 @@ fn build_map_for_rocks(rocks: &HashSet<Index2D>) -> Flat2DArray<Reachability> {
     reachability
 }
 
+pub struct Day21;
+
+impl Puzzle for Day21 {
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-simple_solution!(parse, part1, part2);
\ No newline at end of file
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        parse(&input).map_err(SolveError::from)
+    }
+
+    fn part1(&self, input: &Self::Input) -> usize {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> usize {
+        part2(input)
+    }
+}
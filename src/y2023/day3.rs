@@ -2,6 +2,8 @@ use std::collections::BTreeMap;
 
 use nom::character::complete::u64;
 use nom::IResult;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 struct Coord2D(i64, i64);
@@ -31,7 +33,7 @@ impl GridNumber {
 }
 
 #[derive(Default, Debug)]
-struct Input {
+pub struct Input {
     numbers: BTreeMap<Coord2D, GridNumber>,
     gear_locations: Vec<Coord2D>,
     part_locations: Vec<Coord2D>,
@@ -118,4 +120,22 @@ fn parse(input: &str) -> IResult<&str, Input> {
     Ok(("", target))
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day3;
+
+impl Puzzle for Day3 {
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
@@ -4,9 +4,11 @@ use nom::combinator::{flat_map, map};
 use nom::IResult;
 use nom::multi::{fold_many1, many1, separated_list1};
 use nom::sequence::{preceded, tuple};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, Default)]
-struct Card {
+pub struct Card {
     match_count: u64,
 }
 
@@ -69,4 +71,22 @@ fn part2(input: &Vec<Card>) -> u64 {
     input.into_iter().map(|(_, v)| v).sum()
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day4;
+
+impl Puzzle for Day4 {
+    type Input = Vec<Card>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
@@ -7,6 +7,8 @@ use nom::combinator::map;
 use nom::IResult;
 use nom::multi::{many1, separated_list1};
 use nom::sequence::{preceded, tuple};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug, PartialEq, Eq)]
 struct RangeMapping {
@@ -104,7 +106,7 @@ impl MappingTable {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     ranges: Vec<MappingTable>,
     seeds: Vec<i64>,
 }
@@ -176,4 +178,22 @@ fn part2(input: &Input) -> i64 {
     }).min().expect("At least one")
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day5;
+
+impl Puzzle for Day5 {
+    type Input = Input;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> i64 {
+        part2(input)
+    }
+}
@@ -4,6 +4,8 @@ use nom::combinator::{map, map_res};
 use nom::IResult;
 use nom::multi::{fold_many1, many1};
 use nom::sequence::{preceded, tuple};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::Puzzle;
 
 #[derive(Debug)]
 struct Race {
@@ -79,4 +81,22 @@ fn part2(input: &String) -> u64 {
 
 }
 
-unparsed_solution!(part1, part2);
\ No newline at end of file
+pub struct Day6;
+
+impl Puzzle for Day6 {
+    type Input = String;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, _: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        Ok(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
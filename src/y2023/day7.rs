@@ -6,7 +6,9 @@ use nom::IResult;
 use nom::multi::separated_list1;
 use nom::sequence::tuple;
 
-use crate::day7::ScoreClass::{FiveOfAKind, FourOfAKind, FullHouse, HighCard, Pair, ThreeOfAKind, TwoPair};
+use crate::y2023::day7::ScoreClass::{FiveOfAKind, FourOfAKind, FullHouse, HighCard, Pair, ThreeOfAKind, TwoPair};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 struct Valuation {
@@ -45,7 +47,7 @@ enum CardType {
 
 
 #[derive(Debug, Clone)]
-struct Hand {
+pub struct Hand {
     cards: [CardType; 5],
     bet: u64,
 }
@@ -183,4 +185,22 @@ fn part2(input: &Vec<Hand>) -> u64 {
     sum
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day7;
+
+impl Puzzle for Day7 {
+    type Input = Vec<Hand>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
@@ -9,9 +9,11 @@ use nom::combinator::{map, map_res};
 use nom::IResult;
 use nom::multi::fold_many1;
 use nom::sequence::{terminated, tuple};
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     directions: Vec<Direction>,
     map_nodes: HashMap<Tag, (Tag, Tag)>,
 }
@@ -158,4 +160,22 @@ fn part2(input: &Input) -> u64 {
     overall
 }
 
-nom_solution!(parse, part1, part2);
\ No newline at end of file
+pub struct Day8;
+
+impl Puzzle for Day8 {
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> u64 {
+        part2(input)
+    }
+}
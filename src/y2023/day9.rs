@@ -1,6 +1,8 @@
 use nom::character::complete::{i64, line_ending, space1};
 use nom::IResult;
 use nom::multi::separated_list1;
+use crate::error::{ParseContext, SolveError};
+use crate::puzzle::{nom_parse, Puzzle};
 
 fn parse(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
     separated_list1(line_ending, separated_list1(space1, i64))(input)
@@ -45,5 +47,22 @@ fn solve_part_2(input: &Vec<Vec<i64>>) -> i64{
     input.iter().map(extrapolate_back).sum()
 }
 
+pub struct Day9;
 
-nom_solution!(parse, solve_part_1, solve_part_2);
\ No newline at end of file
+impl Puzzle for Day9 {
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: String, context: &mut ParseContext) -> Result<Self::Input, SolveError> {
+        nom_parse(&input, parse, context)
+    }
+
+    fn part1(&self, input: &Self::Input) -> i64 {
+        solve_part_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> i64 {
+        solve_part_2(input)
+    }
+}